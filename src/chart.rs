@@ -0,0 +1,172 @@
+use chrono::{DateTime, Duration, Utc};
+use image::{Rgba, RgbaImage};
+
+/// How a chart [`Display`](crate::Display) draws its series.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ChartKind {
+    Line,
+    /// Each bar is the average of the values that fall in its slice of the range.
+    Bar,
+}
+
+/// How far back a chart goes, picked with the buttons above it.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub enum ChartRange {
+    Day,
+    Week,
+    #[default]
+    Month,
+    Year,
+}
+
+impl ChartRange {
+    pub const ALL: [ChartRange; 4] = [ChartRange::Day, ChartRange::Week, ChartRange::Month, ChartRange::Year];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            ChartRange::Day => "1D",
+            ChartRange::Week => "1W",
+            ChartRange::Month => "1M",
+            ChartRange::Year => "1Y",
+        }
+    }
+
+    pub fn span(&self) -> Duration {
+        match self {
+            ChartRange::Day => Duration::hours(24),
+            ChartRange::Week => Duration::days(7),
+            ChartRange::Month => Duration::days(30),
+            ChartRange::Year => Duration::days(365),
+        }
+    }
+
+    fn bars(&self) -> usize {
+        match self {
+            ChartRange::Day => 24,
+            ChartRange::Week => 7,
+            ChartRange::Month => 30,
+            ChartRange::Year => 12,
+        }
+    }
+}
+
+const STROKE: f64 = 3.0;
+
+/// Draws the part of `series` within `range` of `end` in `color`, or returns `None` if none of it is.
+pub(crate) fn render(series: &[(DateTime<Utc>, f64)], kind: ChartKind, range: ChartRange, end: DateTime<Utc>, (width, height): (u32, u32), color: [u8; 4]) -> Option<RgbaImage> {
+    let start = end - range.span();
+    let mut points = series.iter().copied().filter(|(t, v)| (start..=end).contains(t) && v.is_finite()).collect::<Vec<_>>();
+    if points.is_empty() { return None; }
+    points.sort_by_key(|(t, _)| *t);
+
+    let span = range.span().num_seconds() as f64;
+    let position = |t: DateTime<Utc>| (t - start).num_seconds() as f64 / span;
+    let mut image = RgbaImage::new(width, height);
+
+    match kind {
+        ChartKind::Line => {
+            let (min, max) = bounds(points.iter().map(|(_, v)| *v));
+            let x = |t: DateTime<Utc>| STROKE + position(t) * (width as f64 - 2.0 * STROKE);
+            let y = |v: f64| STROKE + (1.0 - (v - min) / (max - min)) * (height as f64 - 2.0 * STROKE);
+            let coords = points.iter().map(|(t, v)| (x(*t), y(*v))).collect::<Vec<_>>();
+
+            dot(&mut image, coords[0], color);
+            for pair in coords.windows(2) {
+                let ((x0, y0), (x1, y1)) = (pair[0], pair[1]);
+                let steps = ((x1 - x0).hypot(y1 - y0) * 2.0).ceil().max(1.0) as usize;
+                for step in 1..=steps {
+                    let f = step as f64 / steps as f64;
+                    dot(&mut image, (x0 + (x1 - x0) * f, y0 + (y1 - y0) * f), color);
+                }
+            }
+        },
+        ChartKind::Bar => {
+            let count = range.bars();
+            let mut sums = vec![(0.0, 0); count];
+            for (t, v) in &points {
+                let bar = ((position(*t) * count as f64) as usize).min(count - 1);
+                sums[bar].0 += v;
+                sums[bar].1 += 1;
+            }
+            let means = sums.into_iter().map(|(sum, n)| (n > 0).then(|| sum / n as f64)).collect::<Vec<_>>();
+
+            // Bars grow from zero, so zero is always within the bounds.
+            let (min, max) = bounds(means.iter().flatten().copied().chain([0.0]));
+            let y = |v: f64| (1.0 - (v - min) / (max - min)) * height as f64;
+            let slot = width as f64 / count as f64;
+            for (i, mean) in means.into_iter().enumerate() {
+                let Some(mean) = mean else { continue };
+                let (left, right) = (i as f64 * slot + slot * 0.1, (i + 1) as f64 * slot - slot * 0.1);
+                let top = y(mean).min(y(0.0));
+                let bottom = y(mean).max(y(0.0)).max(top + 1.0);
+                fill(&mut image, (left, top), (right, bottom), color);
+            }
+        },
+    }
+
+    Some(image)
+}
+
+/// The smallest and largest of `values`, spread apart when they are equal so a flat series still has a height.
+fn bounds(values: impl Iterator<Item = f64>) -> (f64, f64) {
+    let (min, max) = values.fold((f64::MAX, f64::MIN), |(min, max), v| (min.min(v), max.max(v)));
+    if max - min < f64::EPSILON { (min - 1.0, max + 1.0) } else { (min, max) }
+}
+
+fn dot(image: &mut RgbaImage, (x, y): (f64, f64), color: [u8; 4]) {
+    let r = STROKE / 2.0;
+    for py in (y - r).floor() as i64..=(y + r).ceil() as i64 {
+        for px in (x - r).floor() as i64..=(x + r).ceil() as i64 {
+            if (px as f64 - x).hypot(py as f64 - y) <= r { put(image, px, py, color); }
+        }
+    }
+}
+
+fn fill(image: &mut RgbaImage, (left, top): (f64, f64), (right, bottom): (f64, f64), color: [u8; 4]) {
+    for py in top.round() as i64..bottom.round() as i64 {
+        for px in left.round() as i64..right.round() as i64 {
+            put(image, px, py, color);
+        }
+    }
+}
+
+fn put(image: &mut RgbaImage, x: i64, y: i64, color: [u8; 4]) {
+    if (0..image.width() as i64).contains(&x) && (0..image.height() as i64).contains(&y) {
+        image.put_pixel(x as u32, y as u32, Rgba(color));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const COLOR: [u8; 4] = [255, 0, 0, 255];
+
+    fn painted(image: &RgbaImage, x: u32, y: u32) -> bool {
+        image.get_pixel(x, y).0 == COLOR
+    }
+
+    #[test]
+    fn bars_average_each_slice() {
+        let end = Utc::now();
+        let series = [
+            (end - Duration::minutes(23 * 60 + 30), 10.0),
+            (end - Duration::minutes(10), 2.0),
+            (end, 8.0),
+        ];
+        // A day has 24 bars, 10 pixels apart at this width.
+        let image = render(&series, ChartKind::Bar, ChartRange::Day, end, (240, 100), COLOR).unwrap();
+
+        assert!(painted(&image, 5, 0) && painted(&image, 5, 99));
+        assert!(!painted(&image, 15, 99));
+        assert!(!painted(&image, 235, 40) && painted(&image, 235, 60) && painted(&image, 235, 99));
+    }
+
+    #[test]
+    fn points_outside_the_range_are_dropped() {
+        let end = Utc::now();
+        let series = [(end - Duration::days(2), 1.0), (end + Duration::hours(1), 1.0), (end, f64::NAN)];
+        assert!(render(&series, ChartKind::Bar, ChartRange::Day, end, (240, 100), COLOR).is_none());
+        assert!(render(&series, ChartKind::Bar, ChartRange::Week, end, (240, 100), COLOR).is_some());
+    }
+}
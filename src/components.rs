@@ -0,0 +1,1002 @@
+use pelican_ui::{drawables, Context, Component};
+use pelican_ui::drawable::{Drawable, Align, Image, ShapeType, Color};
+use pelican_ui::plugin::PelicanUI;
+use pelican_ui::layouts::{Stack, Column, Row, Offset, Size, Padding};
+use pelican_ui::events::{OnEvent, Event, TickEvent, KeyboardEvent, KeyboardState, Key, NamedKey, MouseEvent, MouseState};
+use pelican_ui::components::button::Button;
+use pelican_ui::components::{TextInput, NumericalInput, Icon};
+use pelican_ui::components::list_item::{ListItemSection, ListItemInfoLeft, ListItem as PelicanListItem};
+use pelican_ui::utils::TitleSubtitle;
+use pelican_ui::components::text::{ExpandableText, TextStyle, TextSize};
+
+use image::RgbaImage;
+use chrono::{DateTime, NaiveDateTime, Utc};
+
+use std::rc::Rc;
+use std::time::{Duration, Instant};
+use std::sync::mpsc::{self, Receiver, TryRecvError};
+use std::thread;
+
+use crate::{ValidityFn, ListItem, EnumItem, Mask, Action, Severity, DateRange, DatePreset, Money, Currency, Locale, ChartKind, ChartRange};
+use crate::chart;
+use crate::validators;
+use crate::secret::Secret;
+use crate::providers::Providers;
+use crate::suggestions::{self, SuggestionSource};
+use crate::pages::{refresh, Dependency};
+
+/// Shows the error message of an input's validity check underneath it.
+/// When a tag is given, the message stays hidden until the input has a value.
+#[derive(Component, Debug)]
+pub(crate) struct ValidationMessage(Stack, Option<ExpandableText>, #[skip] Option<String>, #[skip] Box<dyn ValidityFn>, #[skip] Option<String>);
+
+impl ValidationMessage {
+    pub fn new(tag: Option<&str>, check: Box<dyn ValidityFn>) -> Self {
+        ValidationMessage(Stack::default(), None, tag.map(|t| t.to_string()), check, None)
+    }
+}
+
+impl OnEvent for ValidationMessage {
+    fn on_event(&mut self, ctx: &mut Context, event: Box<dyn Event>) -> Vec<Box<dyn Event>> {
+        if event.downcast_ref::<TickEvent>().is_some() {
+            let is_empty = self.2.as_ref().is_some_and(|tag| {
+                let text = ctx.state().get_named::<String>(tag).map(|v| v.is_empty());
+                text.or_else(|| ctx.state().get_named::<Secret>(tag).map(|s| s.is_empty())).unwrap_or(true)
+            });
+            let message = match (self.3)(ctx) {
+                Err(message) if !is_empty && !message.is_empty() => Some(message),
+                _ => None,
+            };
+
+            if message != self.4 {
+                self.1 = message.as_ref().map(|m| ExpandableText::new(ctx, m, TextSize::Sm, TextStyle::Error, Align::Left, None));
+                self.4 = message;
+            }
+        }
+
+        vec![event]
+    }
+}
+
+/// A horizontal run of drawables, such as a row of buttons.
+#[derive(Component, Debug)]
+pub(crate) struct Line(Row, Vec<Box<dyn Drawable>>);
+impl OnEvent for Line {}
+
+impl Line {
+    pub fn new(offset: Offset, items: Vec<Box<dyn Drawable>>) -> Self {
+        Line(Row::new(8.0, offset, Size::Fit, Padding::default()), items)
+    }
+}
+
+/// Whether `event` is a press, and if so whether it landed on the component receiving it.
+/// Inputs that read the keyboard themselves only take keys after being pressed, so typing in one field never reaches another.
+fn pressed(event: &dyn Event) -> Option<bool> {
+    match event.downcast_ref::<MouseEvent>() {
+        Some(MouseEvent {state: MouseState::Pressed, position}) => Some(position.is_some()),
+        _ => None,
+    }
+}
+
+/// The kind of value a [`SecureInput`] collects.
+#[derive(Debug, Clone)]
+pub enum SecureKind {
+    Password,
+    Pin(usize),
+    Phrase(usize, Rc<Vec<String>>),
+}
+
+/// A masked text field, PIN pad or recovery phrase grid writing into a [`Secret`] stored under its tag.
+/// Typed keys only reach it while it is focused.
+#[derive(Component, Debug)]
+pub(crate) struct SecureInput(Column, Vec<Box<dyn Drawable>>, #[skip] SecureKind, #[skip] String, #[skip] String, #[skip] bool, #[skip] Option<(usize, bool)>);
+
+impl SecureInput {
+    pub fn new(ctx: &mut Context, kind: SecureKind, label: &str, tag: &str) -> Self {
+        if ctx.state().get_named::<Secret>(tag).is_none() { Dependency::set_named(ctx.state(), tag, Secret::default()); }
+        let layout = Column::new(16.0, Offset::Center, Size::Fit, Padding::default());
+        let mut input = SecureInput(layout, Vec::new(), kind, label.to_string(), tag.to_string(), false, None);
+        input.update(ctx);
+        input
+    }
+
+    fn revealed_tag(&self) -> String {
+        format!("{}Revealed", self.4)
+    }
+
+    /// Rebuilds the contents whenever the secret's length or visibility changed since the last update.
+    fn update(&mut self, ctx: &mut Context) {
+        let secret = ctx.state().get_named::<Secret>(&self.4).cloned().unwrap_or_default();
+        let revealed = ctx.state().get_named::<bool>(&self.revealed_tag()).copied().unwrap_or_default();
+        if self.6 == Some((secret.len(), revealed)) { return; }
+        self.6 = Some((secret.len(), revealed));
+
+        self.1 = match self.2.clone() {
+            SecureKind::Password => self.password(ctx, &secret, revealed),
+            SecureKind::Pin(length) => self.pin(ctx, &secret, length),
+            SecureKind::Phrase(count, wordlist) => self.phrase(ctx, &secret, count, &wordlist),
+        };
+    }
+
+    fn password(&self, ctx: &mut Context, secret: &Secret, revealed: bool) -> Vec<Box<dyn Drawable>> {
+        let shown = match revealed {
+            true => secret.expose().to_string(),
+            false => "•".repeat(secret.len()),
+        };
+
+        let revealed_tag = self.revealed_tag();
+        let toggle = Box::new(move |ctx: &mut Context| {
+            let revealed = ctx.state().get_named::<bool>(&revealed_tag).copied().unwrap_or_default();
+            ctx.state().set_named(revealed_tag.clone(), !revealed);
+        });
+
+        drawables![
+            ExpandableText::new(ctx, &self.3, TextSize::H5, TextStyle::Heading, Align::Left, None),
+            ExpandableText::new(ctx, &shown, TextSize::Lg, TextStyle::Primary, Align::Left, None),
+            Button::secondary(ctx, Some(if revealed {"block"} else {"monitor"}), if revealed {"Hide"} else {"Show"}, None, toggle, None)
+        ]
+    }
+
+    fn pin(&self, ctx: &mut Context, secret: &Secret, length: usize) -> Vec<Box<dyn Drawable>> {
+        let dots = (0..length).map(|i| if i < secret.len() {"●"} else {"○"}).collect::<Vec<_>>().join(" ");
+        let mut items = drawables![ExpandableText::new(ctx, &dots, TextSize::H3, TextStyle::Heading, Align::Center, None)];
+
+        for keys in [["1", "2", "3"], ["4", "5", "6"], ["7", "8", "9"], ["", "0", "backspace"]] {
+            let buttons = keys.into_iter().filter(|key| !key.is_empty()).map(|key| {
+                let tag = self.4.clone();
+                let on_click = Box::new(move |ctx: &mut Context| edit(ctx, &tag, |secret| match key {
+                    "backspace" => secret.pop(),
+                    digit if secret.len() < length => digit.chars().for_each(|c| secret.push(c)),
+                    _ => {}
+                }));
+
+                Box::new(match key {
+                    "backspace" => Button::secondary(ctx, Some("backspace"), "", None, on_click, None),
+                    digit => Button::secondary(ctx, None, digit, None, on_click, None),
+                }) as Box<dyn Drawable>
+            }).collect::<Vec<_>>();
+
+            items.push(Box::new(Line::new(Offset::Center, buttons)));
+        }
+
+        items
+    }
+
+    fn phrase(&self, ctx: &mut Context, secret: &Secret, count: usize, wordlist: &[String]) -> Vec<Box<dyn Drawable>> {
+        let words = secret.words();
+        let typing = !secret.is_empty() && !secret.expose().ends_with(' ');
+        let partial = typing.then(|| words.last().copied()).flatten().unwrap_or_default();
+
+        let mut items = drawables![ExpandableText::new(ctx, &self.3, TextSize::H5, TextStyle::Heading, Align::Left, None)];
+        for row in (0..count).collect::<Vec<_>>().chunks(3) {
+            let cells = row.iter().map(|i| {
+                let word = words.get(*i).copied().unwrap_or_default();
+                Box::new(ExpandableText::new(ctx, &format!("{}. {word}", i + 1), TextSize::Md, TextStyle::Primary, Align::Left, None)) as Box<dyn Drawable>
+            }).collect::<Vec<_>>();
+            items.push(Box::new(Line::new(Offset::Start, cells)));
+        }
+
+        if !partial.is_empty() {
+            let suggestions = wordlist.iter().filter(|w| w.starts_with(partial) && w.as_str() != partial).take(4).map(|word| {
+                let tag = self.4.clone();
+                let remainder = format!("{} ", &word[partial.len()..]);
+                let on_click = Box::new(move |ctx: &mut Context| edit(ctx, &tag, |secret| remainder.chars().for_each(|c| secret.push(c))));
+                Box::new(Button::secondary(ctx, None, word, None, on_click, None)) as Box<dyn Drawable>
+            }).collect::<Vec<_>>();
+            items.push(Box::new(Line::new(Offset::Start, suggestions)));
+        }
+
+        items
+    }
+
+    fn on_key(&mut self, ctx: &mut Context, key: &Key) {
+        let kind = self.2.clone();
+        edit(ctx, &self.4, |secret| match (key, &kind) {
+            (Key::Named(NamedKey::Backspace), _) => secret.pop(),
+            (Key::Named(NamedKey::Space), SecureKind::Phrase(..)) => next_word(secret, &kind),
+            (Key::Named(NamedKey::Space), SecureKind::Password) => secret.push(' '),
+            (Key::Character(c), SecureKind::Password) => c.chars().for_each(|c| secret.push(c)),
+            (Key::Character(c), SecureKind::Pin(length)) => c.chars().filter(|c| c.is_ascii_digit()).for_each(|c| if secret.len() < *length { secret.push(c) }),
+            (Key::Character(c), SecureKind::Phrase(..)) => c.chars().for_each(|c| match c {
+                ' ' => next_word(secret, &kind),
+                c if c.is_alphabetic() => secret.push(c.to_ascii_lowercase()),
+                _ => {}
+            }),
+            _ => {}
+        });
+    }
+}
+
+/// Ends the word being typed, unless there is none or the phrase is already full.
+fn next_word(secret: &mut Secret, kind: &SecureKind) {
+    let SecureKind::Phrase(count, _) = kind else { return };
+    if !secret.is_empty() && !secret.expose().ends_with(' ') && secret.words().len() < *count {
+        secret.push(' ');
+    }
+}
+
+fn edit(ctx: &mut Context, tag: &str, change: impl FnOnce(&mut Secret)) {
+    let mut secret = ctx.state().get_named::<Secret>(tag).cloned().unwrap_or_default();
+    change(&mut secret);
+    Dependency::set_named(ctx.state(), tag, secret);
+}
+
+impl OnEvent for SecureInput {
+    fn on_event(&mut self, ctx: &mut Context, event: Box<dyn Event>) -> Vec<Box<dyn Event>> {
+        if let Some(inside) = pressed(event.as_ref()) {
+            self.5 = inside;
+        }
+
+        if let Some(KeyboardEvent {state: KeyboardState::Pressed, key}) = event.downcast_ref::<KeyboardEvent>() {
+            if self.5 { self.on_key(ctx, key); }
+        }
+
+        if event.downcast_ref::<TickEvent>().is_some() {
+            self.update(ctx);
+        }
+
+        vec![event]
+    }
+}
+
+/// The items of an enumerator with a radio button each, writing the picked title under its tag.
+/// Unlike a radio selector it can show nothing picked, until the user picks an item.
+#[derive(Component, Debug)]
+pub(crate) struct Choices(Stack, Option<ListItemSection>, #[skip] Vec<EnumItem>, #[skip] String, #[skip] Option<Option<String>>);
+
+impl Choices {
+    pub fn new(ctx: &mut Context, items: Vec<EnumItem>, tag: &str) -> Self {
+        let mut choices = Choices(Stack::default(), None, items, tag.to_string(), None);
+        choices.update(ctx);
+        choices
+    }
+
+    fn update(&mut self, ctx: &mut Context) {
+        let picked = ctx.state().get_named::<String>(&self.3).filter(|v| !v.is_empty()).cloned();
+        if self.4.as_ref() == Some(&picked) { return; }
+        self.4 = Some(picked.clone());
+
+        let list_items = self.2.iter().map(|item| {
+            let (title, data, on_select) = item.get(&self.3);
+            let icon = if picked.as_deref() == Some(title) {"radio_filled"} else {"radio"};
+            PelicanListItem::new(ctx, None, ListItemInfoLeft::new(title, Some(data), None, None), None, None, Some(icon), on_select)
+        }).collect::<Vec<_>>();
+        self.1 = Some(ListItemSection::new(ctx, None, list_items));
+    }
+}
+
+impl OnEvent for Choices {
+    fn on_event(&mut self, ctx: &mut Context, event: Box<dyn Event>) -> Vec<Box<dyn Event>> {
+        if event.downcast_ref::<TickEvent>().is_some() {
+            self.update(ctx);
+        }
+
+        vec![event]
+    }
+}
+
+/// A search field above a filtered list of [`ListItem`]s that writes the picked tags under its own tag.
+#[derive(Component, Debug)]
+pub(crate) struct Picker(Column, TextInput, Option<ListItemSection>, Option<ExpandableText>, #[skip] String, #[skip] Vec<ListItem>, #[skip] bool, #[skip] String, #[skip] Option<(String, Vec<String>)>);
+
+impl Picker {
+    pub fn new(ctx: &mut Context, label: &str, items: Vec<ListItem>, multiple: bool, tag: &str) -> Self {
+        let search_tag = format!("{tag}Search");
+        let query = ctx.state().get_named::<String>(&search_tag).cloned();
+        let search = TextInput::new(ctx, query.as_deref(), ("Search", false), Some(&format!("Search {}...", label.to_lowercase())), None, None, &search_tag);
+        let layout = Column::new(16.0, Offset::Start, Size::Fit, Padding::default());
+        let mut picker = Picker(layout, search, None, None, label.to_string(), items, multiple, tag.to_string(), None);
+        picker.update(ctx);
+        picker
+    }
+
+    fn picked(&self, ctx: &mut Context) -> Vec<String> {
+        match self.6 {
+            true => ctx.state().get_named::<Vec<String>>(&self.7).cloned().unwrap_or_default(),
+            false => ctx.state().get_named::<String>(&self.7).filter(|v| !v.is_empty()).map(|v| vec![v.clone()]).unwrap_or_default(),
+        }
+    }
+
+    /// Rebuilds the list whenever the search query or the picked entries changed since the last update.
+    fn update(&mut self, ctx: &mut Context) {
+        let query = ctx.state().get_named::<String>(&format!("{}Search", self.7)).cloned().unwrap_or_default();
+        let picked = self.picked(ctx);
+        let fingerprint = Some((query.clone(), picked.clone()));
+        if self.8 == fingerprint { return; }
+        self.8 = fingerprint;
+
+        let matches = self.5.iter().filter(|item| item.matches(&query)).cloned().collect::<Vec<_>>();
+        let list_items = matches.iter().map(|item| item.build_pickable(ctx, &self.7, self.6, picked.iter().any(|p| p == item.tag()))).collect::<Vec<_>>();
+
+        (self.2, self.3) = match list_items.is_empty() {
+            true => (None, Some(ExpandableText::new(ctx, &format!("No matches for \"{}\".", query.trim()), TextSize::Md, TextStyle::Secondary, Align::Center, None))),
+            false => (Some(ListItemSection::new(ctx, Some(self.4.clone()), list_items)), None),
+        };
+    }
+}
+
+impl OnEvent for Picker {
+    fn on_event(&mut self, ctx: &mut Context, event: Box<dyn Event>) -> Vec<Box<dyn Event>> {
+        if event.downcast_ref::<TickEvent>().is_some() {
+            self.update(ctx);
+        }
+
+        vec![event]
+    }
+}
+
+/// A live camera preview that decodes QR codes and writes the first decoded text under its tag.
+/// Frames are decoded on a worker thread, one at a time, and the preview is only re-uploaded when the frame changed.
+#[derive(Component, Debug)]
+pub(crate) struct Scanner(Column, Option<Image>, ExpandableText, #[skip] String, #[skip] Option<Instant>, #[skip] Option<Receiver<Option<String>>>, #[skip] Option<RgbaImage>);
+
+impl Scanner {
+    const PREVIEW: f32 = 300.0;
+
+    pub fn new(ctx: &mut Context, instructions: &str, tag: &str) -> Self {
+        let text = ExpandableText::new(ctx, instructions, TextSize::Md, TextStyle::Secondary, Align::Center, None);
+        let layout = Column::new(16.0, Offset::Center, Size::Fit, Padding::default());
+        Scanner(layout, None, text, tag.to_string(), None, None, None)
+    }
+
+    fn update(&mut self, ctx: &mut Context) {
+        if ctx.state().get_named::<String>(&self.3).is_some_and(|v| !v.is_empty()) { return; }
+
+        if let Some(decoding) = &self.5 {
+            match decoding.try_recv() {
+                Ok(Some(text)) => {
+                    self.5 = None;
+                    Dependency::set_named(ctx.state(), &self.3, text);
+                    return;
+                },
+                Ok(None) | Err(TryRecvError::Disconnected) => self.5 = None,
+                Err(TryRecvError::Empty) => {},
+            }
+        }
+
+        let Some(camera) = Providers::camera(ctx) else { return };
+        let frame = camera.borrow_mut().frame(ctx);
+        let Some(frame) = frame else { return };
+
+        // Decoding is far slower than drawing, so only every few frames are decoded.
+        if self.5.is_none() && self.4.is_none_or(|last| last.elapsed() >= Duration::from_millis(250)) {
+            self.4 = Some(Instant::now());
+            let (sender, receiver) = mpsc::channel();
+            let copy = frame.clone();
+            thread::spawn(move || { let _ = sender.send(decode(&copy)); });
+            self.5 = Some(receiver);
+        }
+
+        // Every image added stays in the asset store, so unchanged frames reuse the last one.
+        if self.6.as_ref() == Some(&frame) { return; }
+        let scale = Self::PREVIEW / frame.width().max(frame.height()).max(1) as f32;
+        let size = (frame.width() as f32 * scale, frame.height() as f32 * scale);
+        let image = ctx.assets.add_image(frame.clone());
+        self.1 = Some(Image {shape: ShapeType::Rectangle(0.0, size, 0.0), image, color: None});
+        self.6 = Some(frame);
+    }
+}
+
+/// The text of the first QR code found in `frame`.
+fn decode(frame: &RgbaImage) -> Option<String> {
+    let luma = image::imageops::grayscale(frame);
+    let mut decoder = quircs::Quirc::default();
+    let codes = decoder.identify(luma.width() as usize, luma.height() as usize, luma.as_raw());
+    codes.flatten().find_map(|code| code.decode().ok()).and_then(|data| String::from_utf8(data.payload).ok())
+}
+
+impl OnEvent for Scanner {
+    fn on_event(&mut self, ctx: &mut Context, event: Box<dyn Event>) -> Vec<Box<dyn Event>> {
+        if event.downcast_ref::<TickEvent>().is_some() {
+            self.update(ctx);
+        }
+
+        vec![event]
+    }
+}
+
+/// An on/off row that flips the `bool` stored under its tag when tapped.
+#[derive(Component, Debug)]
+pub(crate) struct Switch(Stack, Option<PelicanListItem>, #[skip] String, #[skip] Option<String>, #[skip] String, #[skip] Option<bool>);
+
+impl Switch {
+    pub fn new(ctx: &mut Context, label: &str, description: Option<&str>, tag: &str) -> Self {
+        let mut switch = Switch(Stack::default(), None, label.to_string(), description.map(|d| d.to_string()), tag.to_string(), None);
+        switch.update(ctx);
+        switch
+    }
+
+    fn update(&mut self, ctx: &mut Context) {
+        let is_on = ctx.state().get_named::<bool>(&self.4).copied().unwrap_or_default();
+        if self.5 == Some(is_on) { return; }
+        self.5 = Some(is_on);
+
+        let tag = self.4.clone();
+        let on_click = Box::new(move |ctx: &mut Context| {
+            let is_on = ctx.state().get_named::<bool>(&tag).copied().unwrap_or_default();
+            Dependency::set_named(ctx.state(), &tag, !is_on);
+        });
+
+        self.1 = Some(PelicanListItem::new(ctx, None,
+            ListItemInfoLeft::new(&self.2, self.3.as_deref(), None, None),
+            Some(TitleSubtitle::new(if is_on {"On"} else {"Off"}, None)),
+            None, None,
+            on_click
+        ));
+    }
+}
+
+impl OnEvent for Switch {
+    fn on_event(&mut self, ctx: &mut Context, event: Box<dyn Event>) -> Vec<Box<dyn Event>> {
+        if event.downcast_ref::<TickEvent>().is_some() {
+            self.update(ctx);
+        }
+
+        vec![event]
+    }
+}
+
+/// A row of mutually exclusive options that writes the picked one under its tag.
+#[derive(Component, Debug)]
+pub(crate) struct Segmented(Column, ExpandableText, Option<Line>, #[skip] Vec<String>, #[skip] String, #[skip] Option<String>);
+
+impl Segmented {
+    pub fn new(ctx: &mut Context, label: &str, options: Vec<String>, tag: &str) -> Self {
+        let text = ExpandableText::new(ctx, label, TextSize::H5, TextStyle::Heading, Align::Left, None);
+        let layout = Column::new(8.0, Offset::Start, Size::Fit, Padding::default());
+        let mut segmented = Segmented(layout, text, None, options, tag.to_string(), None);
+        segmented.update(ctx);
+        segmented
+    }
+
+    fn update(&mut self, ctx: &mut Context) {
+        let selected = ctx.state().get_named::<String>(&self.4).cloned().unwrap_or_default();
+        if self.5.as_ref() == Some(&selected) { return; }
+        self.5 = Some(selected.clone());
+
+        let buttons = self.3.iter().map(|option| {
+            let (tag, value) = (self.4.clone(), option.clone());
+            let on_click = Box::new(move |ctx: &mut Context| Dependency::set_named(ctx.state(), &tag, value.clone()));
+            Box::new(Button::secondary(ctx, (option == &selected).then_some("checkmark"), option, None, on_click, None)) as Box<dyn Drawable>
+        }).collect::<Vec<_>>();
+
+        self.2 = Some(Line::new(Offset::Start, buttons));
+    }
+}
+
+impl OnEvent for Segmented {
+    fn on_event(&mut self, ctx: &mut Context, event: Box<dyn Event>) -> Vec<Box<dyn Event>> {
+        if event.downcast_ref::<TickEvent>().is_some() {
+            self.update(ctx);
+        }
+
+        vec![event]
+    }
+}
+
+/// A text field that applies its [`Mask`], keeps line breaks when multi-line, and counts characters against its limit.
+/// Enter only adds a line break while the field is focused.
+#[derive(Component, Debug)]
+pub(crate) struct TextField(Column, TextInput, Option<ExpandableText>, #[skip] String, #[skip] bool, #[skip] Option<usize>, #[skip] Option<Mask>, #[skip] bool, #[skip] Option<String>);
+
+impl TextField {
+    pub fn new(ctx: &mut Context, label: &str, placeholder: &str, multiline: bool, max_length: Option<usize>, mask: Option<Mask>, tag: &str) -> Self {
+        let value = ctx.state().get_named::<String>(tag).cloned();
+        let input = TextInput::new(ctx, value.as_deref(), (label, false), Some(placeholder), None, None, tag);
+        let layout = Column::new(4.0, Offset::End, Size::Fit, Padding::default());
+        let mut field = TextField(layout, input, None, tag.to_string(), multiline, max_length, mask, false, None);
+        field.update(ctx);
+        field
+    }
+
+    fn value(&self, ctx: &mut Context) -> String {
+        ctx.state().get_named::<String>(&self.3).cloned().unwrap_or_default()
+    }
+
+    /// Writes `value` into the stored value and the text input in place, so the field keeps its focus and cursor.
+    fn set(&mut self, ctx: &mut Context, value: &str) {
+        Dependency::set_named(ctx.state(), &self.3, value.to_string());
+        self.1.set_value(value);
+    }
+
+    fn update(&mut self, ctx: &mut Context) {
+        let mut value = self.value(ctx);
+        if let Some(masked) = self.6.as_ref().map(|m| m.apply(&value)).filter(|m| m != &value) {
+            self.set(ctx, &masked);
+            value = masked;
+        }
+
+        if self.8.as_ref() == Some(&value) { return; }
+        // Typing is the only change worth telling dependents about, not the value the field was built with.
+        if self.8.is_some() { Dependency::touch_named(ctx.state(), &self.3); }
+        self.8 = Some(value.clone());
+
+        self.2 = self.5.map(|max| {
+            let count = value.chars().count();
+            let style = if count > max {TextStyle::Error} else {TextStyle::Secondary};
+            ExpandableText::new(ctx, &format!("{count}/{max}"), TextSize::Sm, style, Align::Right, None)
+        });
+    }
+}
+
+impl OnEvent for TextField {
+    fn on_event(&mut self, ctx: &mut Context, event: Box<dyn Event>) -> Vec<Box<dyn Event>> {
+        if let Some(inside) = pressed(event.as_ref()) {
+            self.7 = inside;
+        }
+
+        if let Some(KeyboardEvent {state: KeyboardState::Pressed, key: Key::Named(NamedKey::Enter)}) = event.downcast_ref::<KeyboardEvent>() {
+            if self.4 && self.7 {
+                let value = format!("{}\n", self.value(ctx));
+                self.set(ctx, &value);
+            }
+        }
+
+        if event.downcast_ref::<TickEvent>().is_some() {
+            self.update(ctx);
+        }
+
+        vec![event]
+    }
+}
+
+/// The candidates of a [`SuggestionSource`] that match the text stored under a tag. Tapping one fills the tag.
+#[derive(Component, Debug)]
+pub(crate) struct Suggestions(Stack, Option<ListItemSection>, #[skip] Box<dyn SuggestionSource>, #[skip] String, #[skip] Option<String>);
+
+impl Suggestions {
+    const LIMIT: usize = 3;
+
+    pub fn new(ctx: &mut Context, source: Box<dyn SuggestionSource>, tag: &str) -> Self {
+        let mut suggestions = Suggestions(Stack::default(), None, source, tag.to_string(), None);
+        suggestions.update(ctx);
+        suggestions
+    }
+
+    fn update(&mut self, ctx: &mut Context) {
+        let query = ctx.state().get_named::<String>(&self.3).cloned().unwrap_or_default();
+        if self.4.as_ref() == Some(&query) { return; }
+        self.4 = Some(query.clone());
+
+        let candidates = self.2.candidates(ctx.state());
+        let items = suggestions::rank(&query, candidates, Self::LIMIT).into_iter().map(|suggestion| {
+            let tag = self.3.clone();
+            let value = suggestion.clone();
+            let on_click = Box::new(move |ctx: &mut Context| {
+                Dependency::set_named(ctx.state(), &tag, value.clone());
+                refresh(ctx);
+            });
+            PelicanListItem::new(ctx, None, ListItemInfoLeft::new(&suggestion, None, None, None), None, None, None, on_click)
+        }).collect::<Vec<_>>();
+
+        self.1 = (!items.is_empty()).then(|| ListItemSection::new(ctx, Some("Suggestions".to_string()), items));
+    }
+}
+
+impl OnEvent for Suggestions {
+    fn on_event(&mut self, ctx: &mut Context, event: Box<dyn Event>) -> Vec<Box<dyn Event>> {
+        if event.downcast_ref::<TickEvent>().is_some() {
+            self.update(ctx);
+        }
+
+        vec![event]
+    }
+}
+
+/// A date field and a time field whose combined value is stored under the tag as an `Option<NaiveDateTime>`.
+#[derive(Component, Debug)]
+pub(crate) struct DateTimeInput(Column, NumericalInput, NumericalInput, #[skip] String);
+
+impl DateTimeInput {
+    pub fn new(ctx: &mut Context, instructions: &str, tag: &str) -> Self {
+        let date = NumericalInput::date(ctx, instructions, &format!("{tag}Date"));
+        let time = NumericalInput::time(ctx, "Time", &format!("{tag}Time"));
+        let layout = Column::new(16.0, Offset::Start, Size::Fit, Padding::default());
+        DateTimeInput(layout, date, time, tag.to_string())
+    }
+}
+
+impl OnEvent for DateTimeInput {
+    fn on_event(&mut self, ctx: &mut Context, event: Box<dyn Event>) -> Vec<Box<dyn Event>> {
+        if event.downcast_ref::<TickEvent>().is_some() {
+            let date = validators::parse_date(&validators::value(ctx, &format!("{}Date", self.3)));
+            let time = validators::parse_time(&validators::value(ctx, &format!("{}Time", self.3)));
+            let value = date.zip(time).map(|(d, t)| d.and_time(t));
+            if ctx.state().get_named::<Option<NaiveDateTime>>(&self.3) != Some(&value) {
+                Dependency::set_named(ctx.state(), &self.3, value);
+            }
+        }
+
+        vec![event]
+    }
+}
+
+/// Start and end date fields, with optional presets, whose combined value is stored under the tag as an `Option<DateRange>`.
+#[derive(Component, Debug)]
+pub(crate) struct DateRangeInput(Column, Option<Line>, NumericalInput, NumericalInput, #[skip] String);
+
+impl DateRangeInput {
+    pub fn new(ctx: &mut Context, instructions: &str, presets: &[DatePreset], tag: &str) -> Self {
+        let buttons = presets.iter().map(|preset| {
+            let (tag, range) = (tag.to_string(), preset.range(DatePreset::today()));
+            let on_click = Box::new(move |ctx: &mut Context| {
+                Dependency::set_named(ctx.state(), &format!("{tag}Start"), range.start.format("%m/%d/%Y").to_string());
+                Dependency::set_named(ctx.state(), &format!("{tag}End"), range.end.format("%m/%d/%Y").to_string());
+                refresh(ctx);
+            });
+            Box::new(Button::secondary(ctx, None, &preset.label(), None, on_click, None)) as Box<dyn Drawable>
+        }).collect::<Vec<_>>();
+
+        let presets = (!buttons.is_empty()).then(|| Line::new(Offset::Start, buttons));
+        let start = NumericalInput::date(ctx, instructions, &format!("{tag}Start"));
+        let end = NumericalInput::date(ctx, "End date", &format!("{tag}End"));
+        let layout = Column::new(16.0, Offset::Start, Size::Fit, Padding::default());
+        DateRangeInput(layout, presets, start, end, tag.to_string())
+    }
+}
+
+impl OnEvent for DateRangeInput {
+    fn on_event(&mut self, ctx: &mut Context, event: Box<dyn Event>) -> Vec<Box<dyn Event>> {
+        if event.downcast_ref::<TickEvent>().is_some() {
+            let start = validators::parse_date(&validators::value(ctx, &format!("{}Start", self.4)));
+            let end = validators::parse_date(&validators::value(ctx, &format!("{}End", self.4)));
+            let value = start.zip(end).map(|(start, end)| DateRange::new(start, end));
+            if ctx.state().get_named::<Option<DateRange>>(&self.4) != Some(&value) {
+                Dependency::set_named(ctx.state(), &self.4, value);
+            }
+        }
+
+        vec![event]
+    }
+}
+
+/// Whether amounts shown with a secondary currency are swapped, so the secondary one is on top. Shared by every
+/// [`Display::currency`](crate::Display), but not by currency inputs, which each keep theirs under `{tag}Swapped`.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub(crate) struct CurrencySwap(bool);
+
+/// A large amount with its value in a second currency underneath, either given or converted with the
+/// registered [`RateProvider`](crate::RateProvider). Tapping swap puts the second currency on top.
+#[derive(Component, Debug)]
+pub(crate) struct CurrencyDisplay(Column, Vec<Box<dyn Drawable>>, #[skip] Money, #[skip] Option<Money>, #[skip] Option<Currency>, #[skip] Option<(Option<Money>, bool, bool)>);
+
+impl CurrencyDisplay {
+    pub fn new(ctx: &mut Context, amount: Money, secondary: Option<Money>, convert_to: Option<Currency>) -> Self {
+        let layout = Column::new(8.0, Offset::Center, Size::Fit, Padding::default());
+        let mut display = CurrencyDisplay(layout, Vec::new(), amount, secondary, convert_to, None);
+        display.update(ctx);
+        display
+    }
+
+    fn update(&mut self, ctx: &mut Context) {
+        let (secondary, is_stale) = match (self.3, self.4) {
+            (Some(secondary), _) => (Some(secondary), false),
+            (None, Some(to)) => match Providers::convert(ctx.state(), self.2, to) {
+                Some((converted, rate)) => (Some(converted), rate.is_stale()),
+                None => (None, false),
+            },
+            (None, None) => (None, false),
+        };
+        let swapped = ctx.state().get_or_default::<CurrencySwap>().0 && secondary.is_some();
+
+        let fingerprint = Some((secondary, swapped, is_stale));
+        if self.5 == fingerprint { return; }
+        self.5 = fingerprint;
+
+        let locale = ctx.state().get::<Locale>().copied().unwrap_or_default();
+        let (top, bottom) = match (swapped, secondary) {
+            (true, Some(secondary)) => (secondary, Some(self.2)),
+            _ => (self.2, secondary),
+        };
+
+        self.1 = drawables![ExpandableText::new(ctx, &top.format(&locale), TextSize::H1, TextStyle::Heading, Align::Center, None)];
+        if let Some(bottom) = bottom {
+            let style = if is_stale {TextStyle::Error} else {TextStyle::Secondary};
+            let text = match is_stale {
+                true => format!("≈ {} · rate out of date", bottom.format(&locale)),
+                false => bottom.format(&locale),
+            };
+            self.1.push(Box::new(ExpandableText::new(ctx, &text, TextSize::Lg, style, Align::Center, None)));
+
+            let on_click = Box::new(|ctx: &mut Context| {
+                let swap = ctx.state().get_or_default::<CurrencySwap>();
+                swap.0 = !swap.0;
+            });
+            let label = format!("Show in {}", bottom.currency().code);
+            self.1.push(Box::new(Button::secondary(ctx, Some("swap"), &label, None, on_click, None)));
+        }
+    }
+}
+
+impl OnEvent for CurrencyDisplay {
+    fn on_event(&mut self, ctx: &mut Context, event: Box<dyn Event>) -> Vec<Box<dyn Event>> {
+        if event.downcast_ref::<TickEvent>().is_some() {
+            self.update(ctx);
+        }
+
+        vec![event]
+    }
+}
+
+/// What the amount typed into a currency input is worth in a second currency, shown underneath it with a button to type
+/// in that currency instead. The amount in the input's own currency is kept under `{tag}Amount` either way, and whether
+/// the input is swapped under `{tag}Swapped`.
+#[derive(Component, Debug)]
+pub(crate) struct ConvertedAmount(Column, Option<ExpandableText>, Option<Button>, #[skip] String, #[skip] Currency, #[skip] Currency, #[skip] Option<(Option<Money>, bool, bool)>);
+
+impl ConvertedAmount {
+    pub fn new(ctx: &mut Context, tag: &str, currency: Currency, to: Currency) -> Self {
+        let layout = Column::new(8.0, Offset::Center, Size::Fit, Padding::default());
+        let mut converted = ConvertedAmount(layout, None, None, tag.to_string(), currency, to, None);
+        converted.update(ctx);
+        converted
+    }
+
+    /// The field typed into, which only shows a dollar sign while dollars are being typed.
+    pub fn input(ctx: &mut Context, instructions: &str, tag: &str, currency: Currency, to: Option<Currency>) -> Box<dyn Drawable> {
+        let entered = match (Self::is_swapped(ctx, tag), to) {
+            (true, Some(to)) => to,
+            _ => currency,
+        };
+        if entered == Currency::USD { return Box::new(NumericalInput::currency(ctx, instructions, tag)); }
+
+        let locale = ctx.state().get::<Locale>().copied().unwrap_or_default();
+        let value = ctx.state().get_named::<String>(tag).cloned();
+        let placeholder = Money::zero(entered).format(&locale);
+        Box::new(TextInput::new(ctx, value.as_deref(), (instructions, false), Some(&placeholder), None, None, tag))
+    }
+
+    fn is_swapped(ctx: &mut Context, tag: &str) -> bool {
+        ctx.state().get_named::<bool>(&format!("{tag}Swapped")).copied().unwrap_or_default()
+    }
+
+    /// Switches the input to typing in `other`, converting what has been typed so far.
+    fn swap_button(ctx: &mut Context, tag: &str, entered: Currency, other: Currency) -> Button {
+        let tag = tag.to_string();
+        let on_click = Box::new(move |ctx: &mut Context| {
+            let locale = ctx.state().get::<Locale>().copied().unwrap_or_default();
+            let typed = Money::parse_in(&validators::value(ctx, &tag), entered, &locale);
+            let converted = typed.and_then(|amount| Providers::convert(ctx.state(), amount, other));
+            let text = converted.map(|(amount, _)| amount.format_plain(&locale)).unwrap_or_default();
+            Dependency::set_named(ctx.state(), &tag, text);
+            let swapped = Self::is_swapped(ctx, &tag);
+            Dependency::set_named(ctx.state(), &format!("{tag}Swapped"), !swapped);
+            refresh(ctx);
+        });
+        Button::secondary(ctx, Some("swap"), &format!("Enter in {}", other.code), None, on_click, None)
+    }
+
+    fn update(&mut self, ctx: &mut Context) {
+        let swapped = Self::is_swapped(ctx, &self.3);
+        let (entered, other) = if swapped {(self.5, self.4)} else {(self.4, self.5)};
+
+        let value = validators::value(ctx, &self.3);
+        let locale = ctx.state().get::<Locale>().copied().unwrap_or_default();
+        let typed = Money::parse_in(&value, entered, &locale);
+        let converted = typed.and_then(|amount| Providers::convert(ctx.state(), amount, other));
+
+        let amount = if swapped {converted.map(|(m, _)| m)} else {typed};
+        let amount_tag = format!("{}Amount", self.3);
+        if ctx.state().get_named::<Option<Money>>(&amount_tag) != Some(&amount) {
+            Dependency::set_named(ctx.state(), &amount_tag, amount);
+        }
+
+        let fingerprint = Some((converted.map(|(m, _)| m), converted.is_some_and(|(_, r)| r.is_stale()), swapped));
+        if self.6 == fingerprint { return; }
+        if self.6.map(|(_, _, s)| s) != Some(swapped) { self.2 = Some(Self::swap_button(ctx, &self.3, entered, other)); }
+        self.6 = fingerprint;
+
+        self.1 = converted.map(|(amount, rate)| {
+            let (text, style) = match rate.is_stale() {
+                true => (format!("≈ {} · rate out of date", amount.format(&locale)), TextStyle::Error),
+                false => (format!("≈ {}", amount.format(&locale)), TextStyle::Secondary),
+            };
+            ExpandableText::new(ctx, &text, TextSize::Md, style, Align::Center, None)
+        });
+    }
+}
+
+impl OnEvent for ConvertedAmount {
+    fn on_event(&mut self, ctx: &mut Context, event: Box<dyn Event>) -> Vec<Box<dyn Event>> {
+        if event.downcast_ref::<TickEvent>().is_some() {
+            self.update(ctx);
+        }
+
+        vec![event]
+    }
+}
+
+/// A chart of a time series with buttons above it for how far back it goes. The range picked is kept under `{label}Range`.
+#[derive(Component, Debug)]
+pub(crate) struct Chart(Column, ExpandableText, Option<Line>, Option<Image>, Option<ExpandableText>, #[skip] ChartKind, #[skip] Vec<(DateTime<Utc>, f64)>, #[skip] String, #[skip] Option<ChartRange>);
+
+impl Chart {
+    const SIZE: (f32, f32) = (320.0, 160.0);
+    /// Charts are drawn at twice their size so lines stay sharp on high density screens.
+    const SCALE: f32 = 2.0;
+
+    pub fn new(ctx: &mut Context, label: &str, kind: ChartKind, series: Vec<(DateTime<Utc>, f64)>) -> Self {
+        let text = ExpandableText::new(ctx, label, TextSize::H5, TextStyle::Heading, Align::Left, None);
+        let layout = Column::new(16.0, Offset::Center, Size::Fit, Padding::default());
+        let mut chart = Chart(layout, text, None, None, None, kind, series, format!("{label}Range"), None);
+        chart.update(ctx);
+        chart
+    }
+
+    fn update(&mut self, ctx: &mut Context) {
+        let range = ctx.state().get_named::<ChartRange>(&self.7).copied().unwrap_or_default();
+        if self.8 == Some(range) { return; }
+        self.8 = Some(range);
+
+        let buttons = ChartRange::ALL.iter().map(|option| {
+            let (tag, option) = (self.7.clone(), *option);
+            let on_click = Box::new(move |ctx: &mut Context| Dependency::set_named(ctx.state(), &tag, option));
+            Box::new(Button::secondary(ctx, (option == range).then_some("checkmark"), option.label(), None, on_click, None)) as Box<dyn Drawable>
+        }).collect::<Vec<_>>();
+        self.2 = Some(Line::new(Offset::Center, buttons));
+
+        let Color(r, g, b, a) = ctx.get::<PelicanUI>().get().0.theme().colors.brand;
+        let pixels = ((Self::SIZE.0 * Self::SCALE) as u32, (Self::SIZE.1 * Self::SCALE) as u32);
+        match chart::render(&self.6, self.5, range, Utc::now(), pixels, [r, g, b, a]) {
+            Some(frame) => {
+                let image = ctx.assets.add_image(frame);
+                self.3 = Some(Image {shape: ShapeType::Rectangle(0.0, Self::SIZE, 0.0), image, color: None});
+                self.4 = None;
+            },
+            None => {
+                self.3 = None;
+                self.4 = Some(ExpandableText::new(ctx, "Nothing to show for this period.", TextSize::Md, TextStyle::Secondary, Align::Center, None));
+            },
+        }
+    }
+}
+
+impl OnEvent for Chart {
+    fn on_event(&mut self, ctx: &mut Context, event: Box<dyn Event>) -> Vec<Box<dyn Event>> {
+        if event.downcast_ref::<TickEvent>().is_some() {
+            self.update(ctx);
+        }
+
+        vec![event]
+    }
+}
+
+/// A message with the icon and color of its [`Severity`], and optional dismiss and action buttons underneath.
+/// Once dismissed it stays hidden for as long as its tag holds `true`.
+#[derive(Component, Debug)]
+pub(crate) struct Banner(Column, Vec<Box<dyn Drawable>>, #[skip] Severity, #[skip] String, #[skip] Option<String>, #[skip] Option<(String, Action)>, #[skip] Option<bool>);
+
+impl Banner {
+    pub fn new(ctx: &mut Context, severity: Severity, text: &str, dismiss: Option<String>, action: Option<(String, Action)>) -> Self {
+        let layout = Column::new(8.0, Offset::Start, Size::Fit, Padding::default());
+        let mut banner = Banner(layout, Vec::new(), severity, text.to_string(), dismiss, action, None);
+        banner.update(ctx);
+        banner
+    }
+
+    fn update(&mut self, ctx: &mut Context) {
+        let dismissed = self.4.as_ref().is_some_and(|tag| ctx.state().get_named::<bool>(tag).copied().unwrap_or_default());
+        if self.6 == Some(dismissed) { return; }
+        self.6 = Some(dismissed);
+
+        self.1 = Vec::new();
+        if dismissed { return; }
+
+        let colors = ctx.get::<PelicanUI>().get().0.theme().colors.clone();
+        let (color, style) = match self.2 {
+            Severity::Info => (colors.text.heading, TextStyle::Primary),
+            Severity::Warning => (colors.status.warning, TextStyle::Primary),
+            Severity::Error => (colors.status.danger, TextStyle::Error),
+            Severity::Success => (colors.status.success, TextStyle::Primary),
+        };
+        let message = drawables![
+            Icon::new(ctx, self.2.icon(), Some(color), 24.0),
+            ExpandableText::new(ctx, &self.3, TextSize::Md, style, Align::Left, None)
+        ];
+        self.1.push(Box::new(Line::new(Offset::Start, message)));
+
+        let mut buttons = Vec::new();
+        if let Some((label, action)) = &self.5 {
+            buttons.push(Box::new(Button::secondary(ctx, None, label, None, action.get(), None)) as Box<dyn Drawable>);
+        }
+        if let Some(tag) = self.4.clone() {
+            let on_click = Box::new(move |ctx: &mut Context| Dependency::set_named(ctx.state(), &tag, true));
+            buttons.push(Box::new(Button::secondary(ctx, Some("close"), "Dismiss", None, on_click, None)));
+        }
+        if !buttons.is_empty() { self.1.push(Box::new(Line::new(Offset::Start, buttons))); }
+    }
+}
+
+impl OnEvent for Banner {
+    fn on_event(&mut self, ctx: &mut Context, event: Box<dyn Event>) -> Vec<Box<dyn Event>> {
+        if event.downcast_ref::<TickEvent>().is_some() {
+            self.update(ctx);
+        }
+
+        vec![event]
+    }
+}
+
+/// The message a [`Toast`] is showing and when it was raised.
+#[derive(Debug, Clone, Default)]
+struct Toasts(Option<(Severity, String, Instant)>);
+
+/// Shows `text` over whatever page is on screen for a few seconds, such as after copying something.
+pub(crate) fn toast(ctx: &mut Context, severity: Severity, text: &str) {
+    ctx.state().set(Toasts(Some((severity, text.to_string(), Instant::now()))));
+}
+
+/// The latest message raised with [`toast`], drawn on top of every page until it times out.
+#[derive(Component, Debug, Default)]
+pub(crate) struct Toast(Stack, Option<Banner>, #[skip] Option<Option<String>>);
+
+impl Toast {
+    const SHOWN_FOR: Duration = Duration::from_secs(3);
+
+    fn update(&mut self, ctx: &mut Context) {
+        let current = ctx.state().get_or_default::<Toasts>().0.clone().filter(|(_, _, raised)| raised.elapsed() < Self::SHOWN_FOR);
+        let fingerprint = Some(current.as_ref().map(|(_, text, _)| text.clone()));
+        if self.2 == fingerprint { return; }
+        self.2 = fingerprint;
+
+        self.1 = current.map(|(severity, text, _)| Banner::new(ctx, severity, &text, None, None));
+    }
+}
+
+impl OnEvent for Toast {
+    fn on_event(&mut self, ctx: &mut Context, event: Box<dyn Event>) -> Vec<Box<dyn Event>> {
+        if event.downcast_ref::<TickEvent>().is_some() {
+            self.update(ctx);
+        }
+
+        vec![event]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::providers::{CameraProvider, FileCamera};
+
+    use std::path::Path;
+
+    /// Saves `data` as a QR code with a quiet zone around it, each module 8 pixels wide.
+    fn save_qr(data: &str, path: &Path) {
+        let code = qrcode::QrCode::new(data).unwrap();
+        let (width, colors) = (code.width() as u32, code.to_colors());
+        let (scale, quiet) = (8, 4);
+        let size = (width + 2 * quiet) * scale;
+        RgbaImage::from_fn(size, size, |x, y| {
+            let (mx, my) = ((x / scale).wrapping_sub(quiet), (y / scale).wrapping_sub(quiet));
+            let dark = mx < width && my < width && colors[(my * width + mx) as usize] == qrcode::Color::Dark;
+            image::Rgba(if dark {[0, 0, 0, 255]} else {[255, 255, 255, 255]})
+        }).save(path).unwrap();
+    }
+
+    #[test]
+    fn decodes_a_code_from_a_file_camera() {
+        let uri = "bitcoin:bc1qar0srrr7xfkvy5l643lydnw9re59gtzzwf5mdq?amount=0.0005&label=Orange";
+        let path = std::env::temp_dir().join("chk-scanner-test.png");
+        save_qr(uri, &path);
+        let frame = FileCamera::new(&path).frame(&mut Context::default());
+        let _ = std::fs::remove_file(&path);
+
+        assert_eq!(frame.as_ref().and_then(decode).as_deref(), Some(uri));
+    }
+
+    #[test]
+    fn passwords_keep_spaces() {
+        let mut ctx = Context::default();
+        let mut input = SecureInput::new(&mut ctx, SecureKind::Password, "Password", "PasswordInput");
+        for key in [Key::Character("a".into()), Key::Named(NamedKey::Space), Key::Character("b".into())] {
+            input.on_key(&mut ctx, &key);
+        }
+
+        assert_eq!(ctx.state().get_named::<Secret>("PasswordInput").map(|s| s.expose().to_string()).as_deref(), Some("a b"));
+    }
+
+    #[test]
+    fn blank_frames_decode_to_nothing() {
+        assert_eq!(decode(&RgbaImage::from_pixel(64, 64, image::Rgba([255, 255, 255, 255]))), None);
+    }
+}
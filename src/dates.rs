@@ -0,0 +1,79 @@
+use chrono::{Datelike, Days, Local, NaiveDate};
+
+/// The dates picked in a date range [`Input`](crate::Input), both ends included.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct DateRange {
+    pub start: NaiveDate,
+    pub end: NaiveDate,
+}
+
+impl DateRange {
+    pub fn new(start: NaiveDate, end: NaiveDate) -> Self {
+        DateRange {start, end}
+    }
+
+    pub fn contains(&self, date: NaiveDate) -> bool {
+        (self.start..=self.end).contains(&date)
+    }
+}
+
+/// A shortcut shown above a date range input that fills both ends relative to today.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DatePreset {
+    Today,
+    /// The last `n` days, including today.
+    LastDays(u64),
+    ThisMonth,
+    ThisYear,
+}
+
+impl DatePreset {
+    pub fn label(&self) -> String {
+        match self {
+            DatePreset::Today => "Today".to_string(),
+            DatePreset::LastDays(n) => format!("Last {n} days"),
+            DatePreset::ThisMonth => "This month".to_string(),
+            DatePreset::ThisYear => "This year".to_string(),
+        }
+    }
+
+    pub fn range(&self, today: NaiveDate) -> DateRange {
+        let start = match self {
+            DatePreset::Today => today,
+            DatePreset::LastDays(n) => today.checked_sub_days(Days::new(n.saturating_sub(1))).unwrap_or(NaiveDate::MIN),
+            DatePreset::ThisMonth => today.with_day(1).unwrap_or(today),
+            DatePreset::ThisYear => today.with_ordinal(1).unwrap_or(today),
+        };
+        DateRange::new(start, today)
+    }
+
+    pub(crate) fn today() -> NaiveDate {
+        Local::now().date_naive()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn date(y: i32, m: u32, d: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(y, m, d).unwrap()
+    }
+
+    #[test]
+    fn presets_end_today() {
+        let today = date(2024, 3, 15);
+        assert_eq!(DatePreset::Today.range(today), DateRange::new(today, today));
+        assert_eq!(DatePreset::ThisMonth.range(today), DateRange::new(date(2024, 3, 1), today));
+        assert_eq!(DatePreset::ThisYear.range(today), DateRange::new(date(2024, 1, 1), today));
+    }
+
+    #[test]
+    fn last_days_includes_today() {
+        let today = date(2024, 3, 15);
+        assert_eq!(DatePreset::LastDays(7).range(today), DateRange::new(date(2024, 3, 9), today));
+        assert_eq!(DatePreset::LastDays(30).range(today), DateRange::new(date(2024, 2, 15), today));
+        assert_eq!(DatePreset::LastDays(1).range(today), DateRange::new(today, today));
+        assert_eq!(DatePreset::LastDays(0).range(today), DateRange::new(today, today));
+    }
+}
@@ -8,35 +8,38 @@ impl Application for RampBuilder { // needs to be a fixed vector of 6 with minim
     fn start(ctx: &mut Context) -> Vec<Root> {
         ctx.state().set(CurrentProject::default());
         ctx.state().set(AllProjects::default());
-        vec![Root::new(RootContent::icon("home"), Home::build(ctx))]
+        vec![Root::new(RootContent::icon("home"), Home::build())]
     }
 
     fn theme(_ctx: &mut Assets) -> Theme { Theme::Dark(Color::from_hex("#eb343a", 255)) }
+}
 
-    fn on_event(ctx: &mut Context, event: Box<dyn Event>) -> Vec<Box<dyn Event>> {
-        if event.downcast_ref::<TickEvent>().is_some() { // change to check for a Save(t) event where t contains the tag
-            ctx.state().get_named::<String>("ProjectNameInput").cloned().and_then(|name| {
-                ctx.state().get_mut::<CurrentProject>().map(|tx| tx.inner.name = name)
-            });
-        }
-
-        vec![event]
+impl Form for CurrentProject {
+    fn fields() -> Vec<Field<Self>> {
+        vec![
+            Field::text("ProjectNameInput", |project: &mut Self, name| project.inner.name = name),
+            Field::value("ProjectAvatarInput", |project: &mut Self, avatar: AvatarContent| project.inner.avatar = avatar),
+            Field::value("LinkedProjectsPicker", |project: &mut Self, linked: Vec<String>| project.inner.linked = linked),
+        ]
     }
 }
 
 #[derive(Debug, Clone)]
 pub struct Home;
 impl Home {
-    fn build(ctx: &mut Context) -> RootPage {
-        let projects = ctx.state().get_or_default::<AllProjects>().inner.iter().map(|project| {
-            ListItem::avatar(project.avatar.clone(), &project.name, &format!("Created {}", &project.date), None, &project.id)
-        }).collect::<Vec<_>>();
-
-        RootPage::new(
+    fn build() -> RootPage {
+        RootPage::dynamic(
             "My projects",
-            vec![
-                Display::list(None, projects, Some(MyProjects::build()), Some("No projects yet.\nGet started by creating a new project."))
-            ],
+            |state: &mut State| {
+                let projects = state.get_or_default::<AllProjects>().inner.iter().map(|project| {
+                    ListItem::avatar(project.avatar.clone(), &project.name, &format!("Created {}", &project.date), None, &project.id)
+                }).collect::<Vec<_>>();
+
+                vec![
+                    Display::list(None, projects, Some(MyProjects::build()), Some("No projects yet.\nGet started by creating a new project.")).with_illustration("brand/illustrations/toucan.svg")
+                ]
+            },
+            vec![Dependency::on::<AllProjects>()],
             None,
             RootBumper::new("New Project", CreateProject::build()),
             None,
@@ -73,9 +76,13 @@ impl MyProjects {
             //     Bumper::default()
             // )),
 
-            Box::new(|_state: &mut State| PageType::display("Orange", 
+            Box::new(|state: &mut State| {
+                let id = state.get_or_default::<Selection>().tag().to_string();
+                let project = state.get_or_default::<AllProjects>().inner.iter().find(|p| p.id == id).cloned().unwrap_or_default();
+                PageType::display(&project.name, 
                 vec![
-                    Display::avatar(AvatarContent::icon("home", AvatarIconStyle::Brand)),
+                    Display::avatar(project.avatar),
+                    Display::bar_chart("Build duration", project.builds),
                     Display::list(Some("Release platforms"), vec![
                         ListItem::avatar(AvatarContent::icon("settings", AvatarIconStyle::Secondary), "iOS", "Default iOS Configuration", None, "ios_default_config"),
                         ListItem::avatar(AvatarContent::icon("settings", AvatarIconStyle::Secondary), "MacOS", "Default MacOS Configuration", None, "macos_default_config"),
@@ -88,7 +95,7 @@ impl MyProjects {
                 Some(("settings".to_string(), EditProject::build())),
                 Bumper::None, 
                 Offset::Start
-            ))
+            )})
         ])
     }
 }
@@ -98,9 +105,9 @@ impl EditProject {
     pub fn build() -> Flow {
         Flow::new(vec![
             Box::new(|_state: &mut State| PageType::settings("Project settings", 
-                AvatarContent::icon("settings", AvatarIconStyle::Secondary), 
-                vec![("Project name".to_string(), "ProjectNameInput".to_string(), Box::new(|ctx: &mut Context| ctx.state().get_mut::<CurrentProject>().map(|p| p.inner.name.is_empty()).unwrap_or_default()) as Box<dyn ValidityFn>)],
-                Bumper::custom("Save", Action::None)
+                AvatarContent::icon("settings", AvatarIconStyle::Secondary), "ProjectAvatarInput",
+                vec![("Project name".to_string(), "ProjectNameInput".to_string(), Box::new(validators::all(vec![Box::new(validators::required("ProjectNameInput")), Box::new(validators::max_length("ProjectNameInput", 32))])) as Box<dyn ValidityFn>)],
+                Bumper::custom("Save", Action::custom(|ctx: &mut Context| CurrentProject::sync(ctx.state())))
             ))
         ])
     }
@@ -110,24 +117,46 @@ impl EditProject {
 pub struct CreateProject;
 impl CreateProject {
     pub fn build() -> Flow {
-        let project = Box::new(|_state: &mut State| PageType::settings("New project", 
-            AvatarContent::icon("settings", AvatarIconStyle::Secondary), 
-            vec![("Project name".to_string(), "ProjectNameInput".to_string(), Box::new(|ctx: &mut Context| ctx.state().get_mut::<CurrentProject>().map(|p| p.inner.name.is_empty()).unwrap_or_default())  as Box<dyn ValidityFn>)],
-            Bumper::default()
-        ));
-
-        let success = |_state: &mut State| PageType::success("Project created", "checkmark", "Project 'orange' created");
-
-        let on_submit = |ctx: &mut Context| println!("Creating project... {:?}", ctx.state().get::<CurrentProject>());
-        Flow::form(vec![Box::new(project)], None, Box::new(success), on_submit)
+        let names = |state: &mut State| state.get_or_default::<AllProjects>().inner.iter().map(|p| p.name.clone()).collect::<Vec<_>>();
+        let project = Box::new(move |_state: &mut State| PageType::inputs("New project", vec![
+            Input::avatar_picker(AvatarContent::icon("settings", AvatarIconStyle::Secondary), "ProjectAvatarInput"),
+            Input::text("Project name", None, "ProjectNameInput", validators::all(vec![Box::new(validators::required("ProjectNameInput")), Box::new(validators::max_length("ProjectNameInput", 32))])).with_suggestions(names),
+        ], Bumper::default()));
+
+        let linked = Box::new(|state: &mut State| {
+            let projects = state.get_or_default::<AllProjects>().inner.iter().map(|project| {
+                ListItem::avatar(project.avatar.clone(), &project.name, &format!("Created {}", &project.date), None, &project.id)
+            }).collect::<Vec<_>>();
+            PageType::input("Linked projects", Input::multi_picker("Projects", projects, 0, "LinkedProjectsPicker"), Bumper::default())
+        });
+
+        let success = |_state: &mut State| PageType::success_with_illustration("Project created", "brand/illustrations/hummingbird.svg", "Project 'orange' created");
+
+        let on_submit = |ctx: &mut Context| {
+            let mut project = ctx.state().get_or_default::<CurrentProject>().inner.clone();
+            println!("Creating project... {:?}", project);
+            project.id = format!("projectid{}", 200 + ctx.state().get_or_default::<AllProjects>().inner.len());
+            project.date = chrono::Local::now().format("%m/%d/%Y").to_string();
+            Dependency::update(ctx.state(), |projects: &mut AllProjects| projects.inner.push(project));
+        };
+        Flow::form(vec![Box::new(project), Box::new(linked)], None, Box::new(success), on_submit).bind::<CurrentProject>()
     }
 }
 
 pub struct IOSSettings;
 impl IOSSettings {
     pub fn build() -> Flow {
-        let page = |_state: &mut State| PageType::input("iOS Settings", 
-            Input::text("Bundle ID", None, "BundleIDInput", |_: &mut Context| {false}), 
+        let page = |_state: &mut State| PageType::inputs("iOS Settings", 
+            vec![
+                Input::text("Bundle ID", None, "BundleIDInput", validators::pattern("BundleIDInput", "[A-Za-z0-9.-]*", "Use only letters, numbers, dots and hyphens.")).with_placeholder("com.example.app"),
+                Input::text("Team ID", None, "TeamIDInput", validators::pattern("TeamIDInput", "[A-Z0-9]{10}", "Team IDs are 10 capital letters or numbers.")),
+                Input::enumerator(vec![
+                    EnumItem::new("iPhone", "Runs on iPhone only"),
+                    EnumItem::new("Universal", "Runs on iPhone and iPad"),
+                ], "DeviceFamilyEnumerator"),
+                Input::text_area("Promotional text", "Tell people what's new...", Some(170), "PromoTextInput", |_: &mut Context| Ok(())),
+                Input::password("App-specific password", "AppPasswordInput", validators::secret_length("AppPasswordInput", 16, 19)),
+            ], 
             Bumper::double(
                 "Debug", Action::custom(|_ctx: &mut Context| {println!("Debug bulid...")}), 
                 "Release", Action::custom(|_ctx: &mut Context| {println!("Release bulid...")})
@@ -5,52 +5,41 @@ pub struct Orange;
 impl Application for Orange { // needs to be a fixed vector of 6 with minimum 1
     fn start(ctx: &mut Context) -> Vec<Root> {
         ctx.state().set(NewTransaction::default());
+        ctx.state().set(Transactions::default());
+        ctx.state().set(Fees::default());
+        Providers::set_rates(ctx, providers::FixedRate::new(Currency::BTC, Money::new(10_000_000, Currency::USD)));
         vec![Root::new(RootContent::icon("wallet"), BitcoinHome::build())]
     }
 
     fn theme(_ctx: &mut Assets) -> Theme { Theme::Dark(Color::from_hex("#eb343a", 255)) }
-
-    fn on_event(ctx: &mut Context, event: Box<dyn Event>) -> Vec<Box<dyn Event>> {
-        if event.downcast_ref::<TickEvent>().is_some() {
-            if let Some(tx) = ctx.state().get_mut::<NewTransaction>() {
-                if let Some(usd_value) = tx.inner.amount.usd() {
-                    tx.inner.amount.btc = format!("{:.8} BTC", usd_value / 1_000_000_000.00);
-                }
-            }
-
-            ctx.state().get_named::<String>("AddressTextInput").cloned().and_then(|address| {
-                ctx.state().get_mut::<NewTransaction>().map(|tx| tx.inner.address = address)
-            });
-
-            ctx.state().get_named::<String>("AmountCurrencyInput").cloned().and_then(|amt| {
-                ctx.state().get_mut::<NewTransaction>().map(|tx| tx.inner.amount.usd = amt)
-            });
-
-            ctx.state().get_named::<String>("FeeEnumerator").cloned().and_then(|val| {
-                ctx.state().get_mut::<NewTransaction>().map(|tx| tx.inner.is_priority = val == "Priority")
-            });
-        }
-
-        vec![event]
-    }
 }
 
 #[derive(Debug, Clone)]
 pub struct BitcoinHome; 
 impl BitcoinHome {
     fn build() -> RootPage {
-        RootPage::new("Wallet", 
-            vec![
-                Display::currency(12.56, "0.00001234 BTC"),
-                Display::list(None, vec![
-                    ListItem::plain("Bitcoin Received", "0.00001234 BTC", Some("$12.45"), "txid0"),
-                    ListItem::plain("Sent Received", "0.00001234 BTC", Some("$12.45"), "txid1"),
-                    ListItem::plain("Sent Received", "0.00001234 BTC", Some("$12.45"), "txid2"),
-                    ListItem::plain("Bitcoin Received", "0.00001234 BTC", Some("$12.45"), "txid3"),
-                    ListItem::plain("Sent Received", "0.00001234 BTC", Some("$12.45"), "txid4"),
-                ], Some(ViewTransaction::build()), None)
-            ], 
-            None,
+        RootPage::dynamic("Wallet", 
+            |state: &mut State| {
+                let history = state.get_or_default::<Transactions>().clone();
+                let range = state.get_named::<Option<DateRange>>("HistoryRange").copied().flatten();
+                let transactions = history.inner.iter().filter(|tx| range.is_none_or(|r| r.contains(tx.date))).map(|tx| {
+                    let title = if tx.is_received {"Bitcoin Received"} else {"Bitcoin Sent"};
+                    ListItem::plain(title, &tx.amount.btc.to_string(), Some(&tx.amount.usd.to_string()), &tx.id)
+                }).collect::<Vec<_>>();
+
+                let (usd, btc) = history.balance();
+                let hidden = state.get_named::<bool>("HideBalanceToggle").copied().unwrap_or_default();
+                vec![
+                    match hidden {
+                        true => Display::banner(Severity::Info, "Your balance is hidden. Show it again in Preferences."),
+                        false => Display::currency(usd, Some(btc)),
+                    },
+                    Display::line_chart("Balance", history.history()),
+                    Display::list(None, transactions, Some(ViewTransaction::build()), None)
+                ]
+            },
+            vec![Dependency::on::<Transactions>(), Dependency::named("HistoryRange"), Dependency::named("HideBalanceToggle")],
+            Some(("settings".to_string(), Box::new(|ctx: &mut Context| (Action::navigate(Preferences::build()).get())(ctx)) as Box<dyn FnMutClone>)),
             RootBumper::new("Receive", Receive::build()),
             Some(RootBumper::new("Send", Send::build())),
         )
@@ -80,6 +69,35 @@ impl BitcoinHome {
 //     }
 // }
 
+pub struct Preferences;
+impl Preferences {
+    pub fn build() -> Flow {
+        let security = Flow::new(vec![Box::new(|_state: &mut State| PageType::input("Change PIN", Input::pin("Enter a new PIN", 6, "PinInput"), Bumper::default()))]);
+        let page = move |_state: &mut State| PageType::preferences("Preferences", vec![
+            Input::segmented("Display unit", vec!["BTC", "sats", "USD"], "UnitSegmented", "USD"),
+            Input::toggle("Hide balance", Some("Hide your balance on the home screen"), "HideBalanceToggle", false),
+            Input::toggle("Notifications", Some("Get notified when you receive bitcoin"), "NotificationsToggle", true),
+            Input::navigation("Security", Some("Change your PIN"), security.clone()),
+            Input::navigation("Filter history", Some("Only show transactions from certain dates"), HistoryFilter::build()),
+        ], Bumper::None);
+
+        Flow::new(vec![Box::new(page)])
+    }
+}
+
+pub struct HistoryFilter;
+impl HistoryFilter {
+    pub fn build() -> Flow {
+        let page = |_state: &mut State| {
+            let presets = vec![DatePreset::LastDays(7), DatePreset::LastDays(30), DatePreset::ThisMonth, DatePreset::ThisYear];
+            let today = chrono::Local::now().date_naive();
+            PageType::input("Filter history", Input::date_range("Start date", presets, "HistoryRange", None, Some(today)), Bumper::default())
+        };
+
+        Flow::new(vec![Box::new(page)])
+    }
+}
+
 pub struct Receive;
 impl Receive {
     pub fn build() -> Flow {
@@ -95,15 +113,16 @@ pub struct ViewTransaction;
 impl ViewTransaction {
     pub fn build() -> Flow {
         Flow::new(vec![Box::new(|state: &mut State| {
-            let tx = &state.get::<NewTransaction>().unwrap().inner;
+            let id = state.get_or_default::<Selection>().tag().to_string();
+            let tx = state.get_or_default::<Transactions>().find(&id);
             let dir = if tx.is_received {"Received"} else {"Sent"};
             PageType::display(&format!("{dir} bitcoin"), vec![
-                Display::currency(12.56, "0.00001234 BTC"),
+                Display::currency(tx.amount.usd, Some(tx.amount.btc)),
                 Display::table("Transcation details", vec![
-                    TableItem::new("Amount Sent (BTC)", &tx.amount.btc),
-                    TableItem::new("Amount Sent", &tx.amount.usd),
-                    TableItem::new("Transaction Fee", &tx.fee),
-                    TableItem::new( "Transaction Total", &tx.total),
+                    TableItem::new("Amount Sent (BTC)", &tx.amount.btc.to_string()),
+                    TableItem::new("Amount Sent", &tx.amount.usd.to_string()),
+                    TableItem::new("Transaction Fee", &tx.fee.to_string()),
+                    TableItem::new( "Transaction Total", &tx.total.to_string()),
                 ])
             ], None, Bumper::Done, Offset::Start)
         })])
@@ -113,70 +132,261 @@ impl ViewTransaction {
 pub struct Send;
 impl Send {
     pub fn build() -> Flow {
-        let address = |_state: &mut State| PageType::input("Bitcoin address", Input::text("Bitcoin address", None, "AddressTextInput", |ctx: &mut Context| {
-            ctx.state().get_mut::<NewTransaction>().map(|tx| tx.inner.address.is_empty()).unwrap_or_default()
-        }), Bumper::default());
-        // Some(vec![
-            // QuickAction::custom("Paste Clipboard", "Pasted", |_ctx: &mut Context| {}),
-            // QuickAction::flow("Scan QR Code", ScanQRCode::new()),
-            // QuickAction::flow("Select Contact", SelectContact::new())
-        // ])
-
-        let amount = |_state: &mut State| PageType::input("Bitcoin amount", Input::currency("Enter send amount", "AmountCurrencyInput", |ctx: &mut Context| {
-            ctx.state().get_mut::<NewTransaction>().map(|tx| tx.inner.amount.usd().map(|u| u <= 0.0).unwrap_or_default()).unwrap_or_default()
-        }), Bumper::default());
-
-        let speed = |_state: &mut State| PageType::input("Transaction speed", Input::enumerator(vec![
-            EnumItem::new("Standard", "Arrives in ~2 hours\n$0.18 bitcoin network fee"),
-            EnumItem::new("Priority", "Arrives in ~30 minutes\n$0.32 bitcoin network fee"),
-        ], "FeeEnumerator"), Bumper::default());
+        let recent = |ctx: &mut Context| ctx.state().get::<Transactions>().and_then(|h| h.inner.iter().find(|tx| !tx.is_received)).map(|tx| tx.address.clone());
+        let actions = vec![
+            QuickAction::paste(),
+            QuickAction::custom("Last recipient", "profile", recent),
+            QuickAction::flow("Scan", "qr_code", ScanAddress::build(), "ScannedAddress"),
+        ];
+
+        let recipients = |state: &mut State| state.get_or_default::<Transactions>().inner.iter().filter(|tx| !tx.is_received).map(|tx| tx.address.clone()).collect::<Vec<_>>();
+        let address = move |_state: &mut State| PageType::input("Bitcoin address", Input::text("Bitcoin address", Some(actions.clone()), "AddressTextInput", validators::all(vec![
+            Box::new(validators::required("AddressTextInput")),
+            Box::new(validators::pattern("AddressTextInput", "[a-zA-Z0-9]{26,62}", "This doesn't look like a bitcoin address.")),
+        ])).with_suggestions(recipients), Bumper::default());
+
+        let amount = |_state: &mut State| PageType::input("Bitcoin amount", Input::currency_converted("Enter send amount", Currency::USD, Currency::BTC, "AmountCurrencyInput", validators::money_range("AmountCurrencyInput", Money::new(1, Currency::USD), Money::new(1_000_000, Currency::USD))), Bumper::default());
+
+        let speed = |state: &mut State| {
+            let fees = state.get_or_default::<Fees>().clone();
+            let last = state.get::<Transactions>().and_then(|h| h.inner.iter().find(|tx| !tx.is_received)).map(|tx| tx.is_priority).unwrap_or_default();
+            let item = |speed: Speed, eta: &str| {
+                EnumItem::new(speed.title(), &format!("Arrives in {eta}\n{} bitcoin network fee", fees.get(speed)))
+            };
+
+            PageType::input("Transaction speed", Input::enumerator_with_default(vec![
+                item(Speed::Standard, "~2 hours"),
+                item(Speed::Priority, "~30 minutes"),
+            ], "FeeEnumerator", if last {Speed::Priority.title()} else {Speed::Standard.title()}), Bumper::default())
+        };
 
         let review = |state: &mut State| {
-            let tx = &state.get::<NewTransaction>().unwrap().inner;
+            let tx = NewTransaction::priced(state);
             let speed = if tx.is_priority {"Priority (~30 mins)"} else {"Standard (~2 hr)"};
             PageType::review("Confirm send", vec![
-                Display::review("Confirm address", &tx.address, "Bitcoin sent to the wrong address can never be recovered."),
+                Display::banner(Severity::Warning, "Bitcoin sent to the wrong address can never be recovered."),
+                Display::review("Confirm address", &tx.address, "Check this matches the address you were given."),
                 Display::table("Confirm amount", vec![
-                    TableItem::new("Amount Sent (BTC)", &tx.amount.btc),
-                    TableItem::new("Amount Sent", &tx.amount.usd),
+                    TableItem::new("Amount Sent (BTC)", &tx.amount.btc.to_string()),
+                    TableItem::new("Amount Sent", &tx.amount.usd.to_string()),
                     TableItem::new("Transaction Speed", speed),
-                    TableItem::new("Transaction Fee", &tx.fee),
-                    TableItem::new( "Transaction Total", &tx.total),
+                    TableItem::new("Transaction Fee", &tx.fee.to_string()),
+                    TableItem::new( "Transaction Total", &tx.total.to_string()),
                 ])
             ])
         };
 
-        let success = |_state: &mut State| PageType::success("Bitcoin sent", "bitcoin", "You sent $10.00");
+        let success = |state: &mut State| {
+            let usd = state.get::<NewTransaction>().map(|tx| tx.inner.amount.usd).unwrap_or_default();
+            PageType::success("Bitcoin sent", "bitcoin", &format!("You sent {usd}"))
+        };
 
-        let on_submit = |ctx: &mut Context| println!("Broadcasting transaction... {:?}", ctx.state().get::<NewTransaction>());
-        Flow::form(vec![Box::new(address), Box::new(amount), Box::new(speed)], Some(Box::new(review)), Box::new(success), on_submit)
+        let on_submit = |ctx: &mut Context| {
+            let tx = NewTransaction::priced(ctx.state());
+            Submission::spawn(move || {
+                println!("Broadcasting transaction... {:?}", tx);
+                std::thread::sleep(std::time::Duration::from_secs(2));
+                Ok(())
+            })
+        };
+        // Only a broadcast that went through makes it into the history, once, however many tries it took.
+        let on_complete = |ctx: &mut Context| {
+            let mut tx = NewTransaction::priced(ctx.state());
+            tx.id = format!("txid{}", ctx.state().get_or_default::<Transactions>().inner.len());
+            tx.date = chrono::Local::now().date_naive();
+            Dependency::update(ctx.state(), |history: &mut Transactions| history.inner.insert(0, tx));
+            ctx.state().set(NewTransaction::default());
+        };
+        let is_small = |state: &mut State| state.get::<NewTransaction>().map(|tx| tx.inner.amount.usd.minor() < 100).unwrap_or_default();
+        Flow::form_deferred(vec![Box::new(address), Box::new(amount), Box::new(speed)], Some(Box::new(review)), Box::new(success), on_submit)
+            .bind::<NewTransaction>()
+            .depends_on(vec![Dependency::on::<Fees>()])
+            .skip_if(2, is_small)
+            .clear_on_cancel()
+            .on_cancel(|ctx: &mut Context| ctx.state().set(NewTransaction::default()))
+            .on_complete(on_complete)
     }
 }
 
 
-#[derive(Clone, Debug, Default)]    
+pub struct ScanAddress;
+impl ScanAddress {
+    pub fn build() -> Flow {
+        let scan = |_state: &mut State| PageType::input("Scan QR code", Input::scan("Point your camera at a bitcoin QR code", "ScannedAddress"), Bumper::default());
+
+        // Scanned codes are usually BIP21 URIs, so keep only the address part.
+        let on_complete = |ctx: &mut Context| {
+            let uri = ctx.state().get_named::<String>("AddressTextInput").cloned().unwrap_or_default();
+            let address = uri.trim_start_matches("bitcoin:").trim_start_matches("BITCOIN:").split('?').next().unwrap_or_default().to_string();
+            ctx.state().set_named("AddressTextInput".to_string(), address);
+            refresh(ctx);
+        };
+
+        Flow::new(vec![Box::new(scan)]).on_complete(on_complete)
+    }
+}
+#[derive(Clone, Copy, Debug)]
 pub struct BitcoinAmount {
-    pub btc: String,
-    pub usd: String,
+    pub btc: Money,
+    pub usd: Money,
 }
 
 impl BitcoinAmount {
-    pub fn usd(&self) -> Option<f32> {
-        self.usd.trim_start_matches('$').replace(',', "").parse::<f32>().ok()
+    /// Converts `usd` at the current rate from the registered [`RateProvider`].
+    pub fn from_usd(state: &mut State, usd: Money) -> Self {
+        let btc = Providers::convert(state, usd, Currency::BTC).map(|(btc, _)| btc).unwrap_or(Money::zero(Currency::BTC));
+        BitcoinAmount {btc, usd}
+    }
+}
+
+impl Default for BitcoinAmount {
+    fn default() -> Self {
+        BitcoinAmount {btc: Money::zero(Currency::BTC), usd: Money::zero(Currency::USD)}
     }
 }
 
 #[derive(Clone, Debug, Default)]
 pub struct Transaction {
+    pub id: String,
     pub address: String,
     pub amount: BitcoinAmount,
     pub is_priority: bool,
-    pub fee: String,
-    pub total: String,
+    pub fee: Money,
+    pub total: Money,
     pub is_received: bool,
+    pub date: chrono::NaiveDate,
 }
 
 #[derive(Clone, Debug, Default)]
 pub struct NewTransaction {
     pub inner: Transaction
 }
+
+impl NewTransaction {
+    /// The transaction being sent, with its bitcoin amount, fee and total worked out from the current rate and [`Fees`].
+    pub fn priced(state: &mut State) -> Transaction {
+        let mut tx = state.get_or_default::<NewTransaction>().inner.clone();
+        tx.amount = BitcoinAmount::from_usd(state, tx.amount.usd);
+        let speed = if tx.is_priority {Speed::Priority} else {Speed::Standard};
+        tx.fee = state.get_or_default::<Fees>().get(speed);
+        tx.total = tx.amount.usd.checked_add(tx.fee).unwrap_or(tx.amount.usd);
+        tx
+    }
+}
+
+impl Form for NewTransaction {
+    fn fields() -> Vec<Field<Self>> {
+        vec![
+            Field::text("AddressTextInput", |tx: &mut Self, address| tx.inner.address = address),
+            Field::money("AmountCurrencyInput", Currency::USD, |tx: &mut Self, usd| tx.inner.amount.usd = usd),
+            Field::parsed("FeeEnumerator", |tx: &mut Self, speed: Speed| tx.inner.is_priority = speed == Speed::Priority),
+        ]
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Speed {
+    Standard,
+    Priority,
+}
+
+impl Speed {
+    pub fn title(&self) -> &'static str {
+        match self {
+            Speed::Standard => "Standard",
+            Speed::Priority => "Priority",
+        }
+    }
+}
+
+impl std::str::FromStr for Speed {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "Standard" => Ok(Speed::Standard),
+            "Priority" => Ok(Speed::Priority),
+            _ => Err(()),
+        }
+    }
+}
+
+/// The current network fee, in USD, for each transaction speed.
+#[derive(Clone, Debug)]
+pub struct Fees {
+    pub standard: Money,
+    pub priority: Money,
+}
+
+impl Fees {
+    pub fn get(&self, speed: Speed) -> Money {
+        match speed {
+            Speed::Standard => self.standard,
+            Speed::Priority => self.priority,
+        }
+    }
+}
+
+impl Default for Fees {
+    fn default() -> Self {
+        Fees {standard: Money::new(18, Currency::USD), priority: Money::new(32, Currency::USD)}
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct Transactions {
+    pub inner: Vec<Transaction>
+}
+
+impl Transactions {
+    pub fn find(&self, id: &str) -> Transaction {
+        self.inner.iter().find(|tx| tx.id == id).cloned().unwrap_or_default()
+    }
+
+    /// The wallet balance as (USD, BTC).
+    pub fn balance(&self) -> (Money, Money) {
+        self.inner.iter().fold((Money::zero(Currency::USD), Money::zero(Currency::BTC)), |(usd, btc), tx| match tx.is_received {
+            true => (usd.checked_add(tx.amount.usd).unwrap_or(usd), btc.checked_add(tx.amount.btc).unwrap_or(btc)),
+            false => (usd.checked_sub(tx.amount.usd).unwrap_or(usd), btc.checked_sub(tx.amount.btc).unwrap_or(btc)),
+        })
+    }
+
+    /// The USD balance after each transaction, oldest first.
+    pub fn history(&self) -> Vec<(chrono::DateTime<chrono::Utc>, f64)> {
+        let mut balance = Money::zero(Currency::USD);
+        self.inner.iter().rev().map(|tx| {
+            balance = match tx.is_received {
+                true => balance.checked_add(tx.amount.usd).unwrap_or(balance),
+                false => balance.checked_sub(tx.amount.usd).unwrap_or(balance),
+            };
+            (tx.date.and_time(chrono::NaiveTime::MIN).and_utc(), balance.to_f64())
+        }).collect()
+    }
+}
+
+impl Default for Transactions {
+    fn default() -> Self {
+        let today = chrono::Local::now().date_naive();
+        let money = |value: &str, currency: Currency| Money::parse(value, currency).unwrap_or(Money::zero(currency));
+        let record = |id: &str, btc: &str, usd: &str, fee: &str, total: &str, is_received: bool, days_ago: u64| Transaction {
+            id: id.to_string(),
+            address: "staesuh8438iy92i984did48i".to_string(),
+            amount: BitcoinAmount {btc: money(btc, Currency::BTC), usd: money(usd, Currency::USD)},
+            is_priority: false,
+            fee: money(fee, Currency::USD),
+            total: money(total, Currency::USD),
+            is_received,
+            date: today - chrono::Days::new(days_ago),
+        };
+
+        Transactions {
+            inner: vec![
+                record("txid0", "0.00001234 BTC", "$12.45", "$0.00", "$12.45", true, 1),
+                record("txid1", "0.00002050 BTC", "$20.68", "$0.18", "$20.86", false, 4),
+                record("txid2", "0.00000512 BTC", "$5.16", "$0.32", "$5.48", false, 12),
+                record("txid3", "0.00010000 BTC", "$100.87", "$0.00", "$100.87", true, 40),
+                record("txid4", "0.00000990 BTC", "$9.98", "$0.18", "$10.16", false, 90),
+            ]
+        }
+    }
+}
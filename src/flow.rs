@@ -1,63 +1,304 @@
 use pelican_ui::{State, Context};
+use pelican_ui::events::Event;
 use pelican_ui::utils::Callback;
 use pelican_ui::components::interface::navigation::NavigationEvent;
 
 use std::cell::RefCell;
 use std::rc::Rc;
 
-use crate::{NavFn};
-use crate::pages::{PageType, BuildablePage};
+use crate::{NavFn, Input};
+use crate::pages::{PageType, BuildablePage, FlowPage, Dependency};
+use crate::form::Form;
+use crate::submission::{Submission, SubmitFn, Pending};
 use crate::FnMutClone;
 
 #[derive(Debug, Clone, Default)]
 pub struct Flow { 
     pages: Vec<Box<dyn PageBuilder>>,
-    on_submit: Option<Box<dyn FnMutClone>>
+    rules: Vec<(usize, Rule)>,
+    bindings: Vec<fn(&mut State)>,
+    dependencies: Vec<Dependency>,
+    hooks: Hooks,
+    on_submit: Option<Box<dyn FnMutClone>>,
+    deferred_submit: Option<Box<dyn SubmitFn>>,
+}
+
+#[derive(Debug, Clone, Default)]
+struct Hooks {
+    on_back: Option<Box<dyn FnMutClone>>,
+    on_cancel: Option<Box<dyn FnMutClone>>,
+    on_complete: Option<Box<dyn FnMutClone>>,
+    clear_on_cancel: bool,
+}
+
+/// A navigation rule attached to a page of a [`Flow`], evaluated right before that page is shown.
+#[derive(Debug, Clone)]
+enum Rule {
+    Skip(Box<dyn Condition>),
+    Branch(Box<dyn Condition>, Flow),
 }
 
 impl Flow {
     pub fn new(pages: Vec<Box<dyn PageBuilder>>) -> Self {
-        Flow { pages, on_submit: None }
+        Flow { pages, ..Default::default() }
     }
 
     pub fn form(mut inputs: Vec<Box<dyn PageBuilder>>, review: Option<Box<dyn PageBuilder>>, success: Box<dyn PageBuilder>, on_submit: impl FnMut(&mut Context) + Clone + 'static) -> Self {
         if let Some(r) = review { inputs.push(r); }
         inputs.push(success);
-        Flow { pages: inputs, on_submit: Some(Box::new(on_submit))}
+        Flow { pages: inputs, on_submit: Some(Box::new(on_submit)), ..Default::default() }
+    }
+
+    /// Like [`Flow::form`], but `on_submit` may take a while or fail. A loading page is shown until the returned
+    /// [`Submission`] finishes, followed by the success page or a failure page offering to retry.
+    pub fn form_deferred(mut inputs: Vec<Box<dyn PageBuilder>>, review: Option<Box<dyn PageBuilder>>, success: Box<dyn PageBuilder>, on_submit: impl FnMut(&mut Context) -> Submission + Clone + 'static) -> Self {
+        if let Some(r) = review { inputs.push(r); }
+        inputs.push(success);
+        Flow { pages: inputs, deferred_submit: Some(Box::new(on_submit)), ..Default::default() }
+    }
+
+    /// Skips the page at `index` whenever `condition` holds, moving straight on to the page after it.
+    /// Skipping the last page finishes the flow.
+    pub fn skip_if(mut self, index: usize, condition: impl FnMut(&mut State) -> bool + Clone + 'static) -> Self {
+        debug_assert!(index < self.pages.len(), "skip_if index {index} is past the last page of the flow");
+        self.rules.push((index, Rule::Skip(Box::new(condition))));
+        self
+    }
+
+    /// Hands navigation over to `flow` instead of showing the page at `index` whenever `condition` holds.
+    ///
+    /// Going back out of `flow` returns to the page before `index`. Closing `flow` from its last page closes this flow
+    /// too, which counts as leaving this flow before its last page.
+    pub fn branch_if(mut self, index: usize, condition: impl FnMut(&mut State) -> bool + Clone + 'static, flow: Flow) -> Self {
+        debug_assert!(index < self.pages.len(), "branch_if index {index} is past the last page of the flow");
+        self.rules.push((index, Rule::Branch(Box::new(condition), flow)));
+        self
+    }
+
+    /// Syncs the inputs of this flow into the [`Form`] `F` each time the flow navigates, before any rules or `on_submit` run.
+    pub fn bind<F: Form>(mut self) -> Self {
+        self.bindings.push(F::sync);
+        self
+    }
+
+    /// Rebuilds whichever page of this flow is showing whenever one of `dependencies` changes.
+    pub fn depends_on(mut self, dependencies: Vec<Dependency>) -> Self {
+        self.dependencies.extend(dependencies);
+        self
+    }
+
+    /// Runs every time the user goes back a page within this flow, including when backing out of its first page.
+    pub fn on_back(mut self, on_back: impl FnMut(&mut Context) + Clone + 'static) -> Self {
+        self.hooks.on_back = Some(Box::new(on_back));
+        self
+    }
+
+    /// Runs when the user leaves this flow before reaching its last page.
+    pub fn on_cancel(mut self, on_cancel: impl FnMut(&mut Context) + Clone + 'static) -> Self {
+        self.hooks.on_cancel = Some(Box::new(on_cancel));
+        self
+    }
+
+    /// Runs when the user closes this flow from its last page.
+    pub fn on_complete(mut self, on_complete: impl FnMut(&mut Context) + Clone + 'static) -> Self {
+        self.hooks.on_complete = Some(Box::new(on_complete));
+        self
+    }
+
+    /// Resets the values stored under the tags of every input shown by this flow when it is cancelled.
+    pub fn clear_on_cancel(mut self) -> Self {
+        self.hooks.clear_on_cancel = true;
+        self
     }
  
     pub(crate) fn build(&mut self) -> Callback {
         let length = self.pages.len();
         if self.pages.is_empty() { return Box::new(|_ctx| {}); }
 
-        let mut pages = self.pages.clone();
-        let mut first = pages.remove(0);
         let mut next_fn: Option<NavFn> = None;
 
-        for (i, page) in pages.into_iter().rev().enumerate() {
-            let callback = (i == 0).then_some(self.on_submit.clone()).flatten(); 
+        for (i, page) in self.pages.clone().into_iter().enumerate().rev() {
+            let callback = (i == length - 1 && i > 0).then(|| self.on_submit.clone()).flatten(); 
+            let rules = self.rules.iter().filter(|(index, _)| *index == i).map(|(_, rule)| rule.clone()).collect::<Vec<_>>();
+            let bindings = self.bindings.clone();
+            let dependencies = self.dependencies.clone();
+            let is_last = i == length - 1;
+            let deferred = (is_last && i > 0).then(|| self.deferred_submit.clone()).flatten();
             let next = next_fn.take();
-            let mut page = page;
-            next_fn = Some(Rc::new(RefCell::new(move |ctx: &mut Context| {
+            let syncs = bindings.clone();
+            let show: NavFn = Rc::new(RefCell::new(move |ctx: &mut Context| {
+                bindings.iter().for_each(|sync| sync(ctx.state()));
                 if let Some(cb) = callback.clone() { (cb.clone())(ctx) }
-                let mut x = (page)(ctx.state());
-                *x.flow_length() = length;
-                *x.get_nav() = next.clone();
-                let page_box = x.build(ctx);
+
+                for rule in rules.clone() {
+                    match rule {
+                        Rule::Skip(mut condition) => if (condition)(ctx.state()) {
+                            match next.as_ref() {
+                                Some(n) => (n.borrow_mut())(ctx),
+                                None => ActiveFlows::complete_top(ctx),
+                            }
+                            return;
+                        },
+                        Rule::Branch(mut condition, mut flow) => if (condition)(ctx.state()) {
+                            let index = ctx.state().get_or_default::<ActiveFlows>().0.len();
+                            (flow.build())(ctx);
+                            ActiveFlows::branched(ctx, index);
+                            return;
+                        }
+                    }
+                }
+
+                // The pages this flow has on screen once this one is pushed, leaving out any that were skipped.
+                let shown = ActiveFlows::depth(ctx) + 1;
+                let mut flow_page = FlowPage::new(page.clone(), shown, next.clone(), dependencies.clone());
+                let page_box = flow_page.build(ctx);
+                ActiveFlows::pushed(ctx, flow_page.inputs(), is_last);
                 ctx.trigger_event(NavigationEvent::Push(Some(Box::new(page_box))));
-            })));
+            }));
+
+            next_fn = Some(match deferred {
+                Some(submit) => Rc::new(RefCell::new(move |ctx: &mut Context| {
+                    syncs.iter().for_each(|sync| sync(ctx.state()));
+                    Pending::submit(ctx, submit.clone(), show.clone());
+                })),
+                None => show,
+            });
         }
 
+        let first = next_fn.expect("flow has at least one page");
+        let hooks = self.hooks.clone();
         Box::new(move |ctx: &mut Context| {
-            let mut x = (first)(ctx.state());
-            *x.flow_length() = length;
-            *x.get_nav() = next_fn.clone();
-            let page_box = x.build(ctx);
-            ctx.trigger_event(NavigationEvent::Push(Some(Box::new(page_box))));
+            ActiveFlows::start(ctx, hooks.clone());
+            (first.borrow_mut())(ctx)
         })
     }
 }
 
+/// The flows currently on screen, innermost last, so their hooks can run as the user leaves them.
+///
+/// Pops chk triggers itself are counted as soon as they are triggered, and the second field holds how many of
+/// them are still to arrive, so [`ActiveFlows::back`] only handles the pops made by the user.
+#[derive(Debug, Clone, Default)]
+pub(crate) struct ActiveFlows(Vec<ActiveFlow>, usize);
+
+#[derive(Debug, Clone)]
+struct ActiveFlow {
+    hooks: Hooks,
+    depth: usize,
+    inputs: Vec<Input>,
+    is_complete: bool,
+    /// Started by a [`Flow::branch_if`] rule, so completing it closes the flow below as well.
+    branched: bool,
+}
+
+impl ActiveFlow {
+    fn finish(self, ctx: &mut Context) {
+        let ActiveFlow {hooks, inputs, is_complete, ..} = self;
+        match is_complete {
+            true => if let Some(mut on_complete) = hooks.on_complete { (on_complete)(ctx) },
+            false => {
+                if hooks.clear_on_cancel { inputs.iter().for_each(|input| input.clear(ctx.state())); }
+                if let Some(mut on_cancel) = hooks.on_cancel { (on_cancel)(ctx) }
+            }
+        }
+
+        // Secrets never outlive the flow that collected them.
+        inputs.iter().filter(|input| input.is_secure()).for_each(|input| input.clear(ctx.state()));
+    }
+}
+
+impl ActiveFlows {
+    fn start(ctx: &mut Context, hooks: Hooks) {
+        ctx.state().get_or_default::<ActiveFlows>().0.push(ActiveFlow {hooks, depth: 0, inputs: Vec::new(), is_complete: false, branched: false});
+    }
+
+    pub(crate) fn pushed(ctx: &mut Context, inputs: Vec<Input>, is_last: bool) {
+        if let Some(flow) = ctx.state().get_or_default::<ActiveFlows>().0.last_mut() {
+            flow.depth += 1;
+            flow.inputs.extend(inputs);
+            flow.is_complete |= is_last;
+        }
+    }
+
+    /// How many pages the innermost flow has on screen.
+    pub(crate) fn depth(ctx: &mut Context) -> usize {
+        ctx.state().get_or_default::<ActiveFlows>().0.last().map(|flow| flow.depth).unwrap_or_default()
+    }
+
+    /// Marks the flow at `index` as started by a branch, unless it already closed.
+    fn branched(ctx: &mut Context, index: usize) {
+        if let Some(flow) = ctx.state().get_or_default::<ActiveFlows>().0.get_mut(index) { flow.branched = true; }
+    }
+
+    /// Marks the innermost flow as complete, finishes it and backs out of all of its pages, along with the pages of
+    /// the flows it branched from.
+    pub(crate) fn complete_top(ctx: &mut Context) {
+        let flows = ctx.state().get_or_default::<ActiveFlows>();
+        let mut closed = Vec::new();
+        while let Some(mut flow) = flows.0.pop() {
+            flow.is_complete |= closed.is_empty();
+            let branched = flow.branched;
+            closed.push(flow);
+            if !branched { break; }
+        }
+
+        let depth = closed.iter().map(|flow| flow.depth).sum::<usize>();
+        flows.1 += depth;
+        closed.into_iter().for_each(|flow| flow.finish(ctx));
+        (0..depth).for_each(|_| ctx.trigger_event(NavigationEvent::Pop));
+    }
+
+    /// Pops the top page without running the `on_back` hook, for pages chk replaces itself.
+    pub(crate) fn pop(ctx: &mut Context) {
+        let flows = ctx.state().get_or_default::<ActiveFlows>();
+        flows.1 += 1;
+        if let Some(flow) = flows.0.last_mut() { flow.depth = flow.depth.saturating_sub(1); }
+        ctx.trigger_event(NavigationEvent::Pop);
+    }
+
+
+    /// Closes the innermost flow from its last page, or pops `fallback` pages when no flow is showing.
+    pub(crate) fn done(ctx: &mut Context, fallback: usize) {
+        match Self::depth(ctx) {
+            0 => (0..fallback).for_each(|_| ctx.trigger_event(NavigationEvent::Pop)),
+            _ => Self::complete_top(ctx),
+        }
+    }
+
+    pub(crate) fn on_event(ctx: &mut Context, event: &dyn Event) {
+        match event.downcast_ref::<NavigationEvent>() {
+            Some(NavigationEvent::Pop) => Self::back(ctx),
+            Some(NavigationEvent::Reset) => Self::exit(ctx),
+            _ => {}
+        }
+    }
+
+    fn back(ctx: &mut Context) {
+        let flows = ctx.state().get_or_default::<ActiveFlows>();
+        if flows.1 > 0 {
+            flows.1 -= 1;
+            return;
+        }
+
+        // Flows that branched away before showing a page never reach the screen.
+        flows.0.retain(|flow| flow.depth > 0);
+        let Some(flow) = flows.0.last_mut() else { return };
+        flow.depth -= 1;
+        let flow = match flow.depth {
+            0 => flows.0.pop().unwrap(),
+            _ => flow.clone(),
+        };
+
+        if let Some(mut on_back) = flow.hooks.on_back.clone() { (on_back)(ctx) }
+        if flow.depth == 0 { flow.finish(ctx) }
+    }
+
+    fn exit(ctx: &mut Context) {
+        let ActiveFlows(flows, _) = std::mem::take(ctx.state().get_or_default::<ActiveFlows>());
+        flows.into_iter().rev().filter(|flow| flow.depth > 0).for_each(|flow| flow.finish(ctx));
+    }
+}
+
 pub trait PageBuilder: FnMut(&mut State) -> PageType + 'static {
     fn clone_box(&self) -> Box<dyn PageBuilder>;
 }
@@ -79,3 +320,89 @@ impl std::fmt::Debug for dyn PageBuilder {
         write!(f, "Page Builder...")
     }
 }
+
+pub trait Condition: FnMut(&mut State) -> bool + 'static {
+    fn clone_box(&self) -> Box<dyn Condition>;
+}
+
+impl<F> Condition for F where F: FnMut(&mut State) -> bool + Clone + 'static {
+    fn clone_box(&self) -> Box<dyn Condition> {
+        Box::new(self.clone())
+    }
+}
+
+impl Clone for Box<dyn Condition> {
+    fn clone(&self) -> Self {
+        self.as_ref().clone_box()
+    }
+}
+
+impl std::fmt::Debug for dyn Condition {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Condition...")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn success_page_counts_only_pages_on_screen() {
+        let mut ctx = Context::default();
+        let review = |_state: &mut State| PageType::review("Review", Vec::new());
+        (Flow::new(vec![Box::new(review)]).build())(&mut ctx);
+        assert_eq!(ActiveFlows::depth(&mut ctx), 1);
+
+        // The success page is given the flow length the same way a flow's pages are.
+        let lengths = Rc::new(RefCell::new(Vec::new()));
+        let record = lengths.clone();
+        let success: NavFn = Rc::new(RefCell::new(move |ctx: &mut Context| record.borrow_mut().push(ActiveFlows::depth(ctx) + 1)));
+        Pending::submit(&mut ctx, Box::new(|_ctx: &mut Context| Submission::ready(Ok(()))), success);
+        assert_eq!(ActiveFlows::depth(&mut ctx), 2);
+
+        Pending::poll(&mut ctx);
+        assert_eq!(ActiveFlows::depth(&mut ctx), 1);
+        assert_eq!(*lengths.borrow(), vec![2]);
+    }
+
+    #[test]
+    fn done_does_not_count_as_going_back() {
+        let mut ctx = Context::default();
+        let calls = Rc::new(RefCell::new(Vec::new()));
+        let (backs, completes) = (calls.clone(), calls.clone());
+        let page = |_state: &mut State| PageType::review("Review", Vec::new());
+        let mut flow = Flow::new(vec![Box::new(page)])
+            .on_back(move |_ctx: &mut Context| backs.borrow_mut().push("back"))
+            .on_complete(move |_ctx: &mut Context| completes.borrow_mut().push("complete"));
+        (flow.build())(&mut ctx);
+
+        ActiveFlows::done(&mut ctx, 1);
+        ActiveFlows::on_event(&mut ctx, &NavigationEvent::Pop);
+
+        assert_eq!(*calls.borrow(), vec!["complete"]);
+        assert_eq!(ActiveFlows::depth(&mut ctx), 0);
+    }
+
+    #[test]
+    fn closing_a_branch_closes_the_flow_it_left() {
+        let mut ctx = Context::default();
+        let calls = Rc::new(RefCell::new(Vec::new()));
+        let (branch_done, outer_cancelled, outer_done) = (calls.clone(), calls.clone(), calls.clone());
+        let page = |_state: &mut State| PageType::review("Review", Vec::new());
+
+        let kyc = Flow::new(vec![Box::new(page)]).on_complete(move |_ctx: &mut Context| branch_done.borrow_mut().push("branch complete"));
+        let mut flow = Flow::new(vec![Box::new(page), Box::new(page)])
+            .branch_if(0, |_state: &mut State| true, kyc)
+            .on_cancel(move |_ctx: &mut Context| outer_cancelled.borrow_mut().push("outer cancel"))
+            .on_complete(move |_ctx: &mut Context| outer_done.borrow_mut().push("outer complete"));
+        (flow.build())(&mut ctx);
+        assert_eq!(ActiveFlows::depth(&mut ctx), 1);
+
+        ActiveFlows::done(&mut ctx, 1);
+        ActiveFlows::on_event(&mut ctx, &NavigationEvent::Pop);
+
+        assert_eq!(*calls.borrow(), vec!["branch complete", "outer cancel"]);
+        assert!(ctx.state().get_or_default::<ActiveFlows>().0.is_empty());
+    }
+}
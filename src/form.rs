@@ -0,0 +1,101 @@
+use pelican_ui::State;
+use chrono::{NaiveDate, NaiveTime};
+
+use std::rc::Rc;
+use std::str::FromStr;
+
+use crate::validators::{number, money, parse_date, parse_time};
+use crate::money::{Money, Currency};
+use crate::pages::Dependency;
+
+/// A typed model filled from [`Input`](crate::Input) tags.
+///
+/// Bind it to a flow with [`Flow::bind`](crate::Flow::bind) and the values entered on each page are
+/// parsed and written into the struct stored in [`State`] whenever the flow moves forward.
+pub trait Form: Clone + Default + std::fmt::Debug + 'static {
+    fn fields() -> Vec<Field<Self>>;
+
+    /// Writes every bound input that currently holds a parseable value into the stored form.
+    fn sync(state: &mut State) {
+        let mut form = state.get_or_default::<Self>().clone();
+        Self::fields().iter().for_each(|field| (field.write)(&mut form, state));
+        Dependency::set(state, form);
+    }
+}
+
+/// Maps the value stored under an input's tag onto a field of `F`.
+#[derive(Clone)]
+pub struct Field<F> {
+    tag: String,
+    write: Rc<dyn Fn(&mut F, &mut State)>,
+}
+
+impl<F: 'static> Field<F> {
+    pub fn text(tag: &str, write: impl Fn(&mut F, String) + 'static) -> Self {
+        Self::parse(tag, |v| Some(v.to_string()), write)
+    }
+
+    /// Reads a currency input as a number, ignoring the currency symbol and thousands separators.
+    pub fn currency(tag: &str, write: impl Fn(&mut F, f64) + 'static) -> Self {
+        Self::parse(tag, number, write)
+    }
+
+    /// Reads a currency input as an exact [`Money`] amount, skipping values with more decimals than `currency` has.
+    pub fn money(tag: &str, currency: Currency, write: impl Fn(&mut F, Money) + 'static) -> Self {
+        let name = tag.to_string();
+        Field {
+            tag: tag.to_string(),
+            write: Rc::new(move |form: &mut F, state: &mut State| {
+                if let Some(value) = money(state, &name, currency) { write(form, value) }
+            }),
+        }
+    }
+
+    pub fn date(tag: &str, write: impl Fn(&mut F, NaiveDate) + 'static) -> Self {
+        Self::parse(tag, parse_date, write)
+    }
+
+    pub fn time(tag: &str, write: impl Fn(&mut F, NaiveTime) + 'static) -> Self {
+        Self::parse(tag, parse_time, write)
+    }
+
+    /// Reads any value implementing [`FromStr`], such as an enum matching the titles of an enumerator's items.
+    pub fn parsed<T: FromStr + 'static>(tag: &str, write: impl Fn(&mut F, T) + 'static) -> Self {
+        Self::parse(tag, |v| v.parse::<T>().ok(), write)
+    }
+
+    pub fn toggle(tag: &str, write: impl Fn(&mut F, bool) + 'static) -> Self {
+        Self::value(tag, write)
+    }
+
+    /// Reads a value stored under the tag as-is, such as the [`AvatarContent`](crate::AvatarContent) of an avatar picker.
+    pub fn value<T: Clone + 'static>(tag: &str, write: impl Fn(&mut F, T) + 'static) -> Self {
+        let name = tag.to_string();
+        Field {
+            tag: tag.to_string(),
+            write: Rc::new(move |form: &mut F, state: &mut State| {
+                if let Some(value) = state.get_named::<T>(&name).cloned() { write(form, value) }
+            }),
+        }
+    }
+
+    pub fn tag(&self) -> &str {
+        &self.tag
+    }
+
+    fn parse<T>(tag: &str, parse: impl Fn(&str) -> Option<T> + 'static, write: impl Fn(&mut F, T) + 'static) -> Self {
+        let name = tag.to_string();
+        Field {
+            tag: tag.to_string(),
+            write: Rc::new(move |form: &mut F, state: &mut State| {
+                if let Some(value) = state.get_named::<String>(&name).and_then(|v| parse(v)) { write(form, value) }
+            }),
+        }
+    }
+}
+
+impl<F> std::fmt::Debug for Field<F> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Field({})", self.tag)
+    }
+}
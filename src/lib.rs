@@ -5,8 +5,28 @@ use structs::*;
 mod state;
 pub mod examples;
 mod flow;
-pub use flow::{PageBuilder};
+pub use flow::{PageBuilder, Condition};
+mod form;
+pub use form::{Form, Field};
+mod submission;
+pub use submission::Submission;
 mod pages;
+mod components;
+pub use components::SecureKind;
+mod secret;
+pub use secret::Secret;
+mod dates;
+pub mod money;
+pub use money::{Money, Currency, Locale, Rate};
+pub use dates::{DateRange, DatePreset};
+mod chart;
+pub use chart::{ChartKind, ChartRange};
+pub mod validators;
+pub mod suggestions;
+pub use suggestions::SuggestionSource;
+pub use validators::Validation;
+pub mod providers;
+pub use providers::{Providers, ShareProvider, ImagePicker, ClipboardProvider, CameraProvider, RateProvider};
 
 pub use chk::flow::Flow;
 
@@ -15,9 +35,15 @@ pub use chk::structs::{
     RootContent,
     Display,
     ListItem,
+    Selection,
     Action,
+    QuickAction,
     TableItem,
     Input,
+    Mask,
+    ImageSource,
+    ImageFit,
+    Severity,
     EnumItem,
     ChecklistItem,
 };
@@ -29,6 +55,8 @@ pub use chk::pages::{
     RootBumper,
     AppPage,
     BuildablePage,
+    Dependency,
+    refresh,
 };
 
 pub use pelican_ui::{
@@ -72,6 +100,10 @@ pub mod __private {
     use pelican_ui::components::interface::navigation::RootInfo;
 
     use crate::pages::BuildablePage;
+    use crate::flow::ActiveFlows;
+    use crate::submission::Pending;
+    use crate::structs::Returning;
+    use pelican_ui::events::TickEvent;
 
     pub struct CHK<A: Application>(A);
 
@@ -97,6 +129,11 @@ pub mod __private {
         }
 
         fn on_event(_interface: &mut Interface, ctx: &mut Context, event: Box<dyn Event>) -> Vec<Box<dyn Event>> {
+            ActiveFlows::on_event(ctx, event.as_ref());
+            if event.downcast_ref::<TickEvent>().is_some() {
+                Pending::poll(ctx);
+                Returning::poll(ctx);
+            }
             A::on_event(ctx, event)
         }
     }
@@ -0,0 +1,315 @@
+//! Fixed-point amounts of money.
+//!
+//! A [`Money`] counts whole minor units of its [`Currency`] (cents, satoshis, ...), so amounts never pick
+//! up floating point rounding errors. It is formatted for display with a [`Locale`], which apps can store
+//! in [`State`](crate::State) to change how every amount chk shows is written.
+
+use chrono::{DateTime, Duration, Utc};
+
+use std::fmt;
+
+/// A currency and how many decimal places its minor unit has.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Currency {
+    pub code: &'static str,
+    /// Written before or after the amount depending on the [`Locale`]. Without one, the code is written after it.
+    pub symbol: Option<&'static str>,
+    pub decimals: u32,
+}
+
+impl Currency {
+    pub const USD: Currency = Currency::new("USD", Some("$"), 2);
+    pub const EUR: Currency = Currency::new("EUR", Some("€"), 2);
+    pub const GBP: Currency = Currency::new("GBP", Some("£"), 2);
+    pub const JPY: Currency = Currency::new("JPY", Some("¥"), 0);
+    pub const BTC: Currency = Currency::new("BTC", None, 8);
+    pub const SATS: Currency = Currency::new("sats", None, 0);
+
+    pub const fn new(code: &'static str, symbol: Option<&'static str>, decimals: u32) -> Self {
+        Currency {code, symbol, decimals}
+    }
+
+    pub(crate) fn scale(&self) -> i128 {
+        10i128.pow(self.decimals)
+    }
+
+    /// `value` without this currency's symbol or code at its start, or at its end when `leading` is false.
+    fn strip<'a>(&self, value: &'a str, leading: bool) -> &'a str {
+        for marker in self.symbol.into_iter().chain([self.code]) {
+            let (edge, remainder) = match leading {
+                true => (value.get(..marker.len()), value.get(marker.len()..)),
+                false => (value.get(value.len().saturating_sub(marker.len())..), value.get(..value.len().saturating_sub(marker.len()))),
+            };
+            if let (Some(edge), Some(remainder)) = (edge, remainder) {
+                if edge.eq_ignore_ascii_case(marker) { return remainder; }
+            }
+        }
+        value
+    }
+}
+
+impl Default for Currency {
+    fn default() -> Self {
+        Currency::USD
+    }
+}
+
+/// How amounts are written: the separators used and which side the currency symbol goes on.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Locale {
+    pub group: char,
+    pub decimal: char,
+    pub symbol_first: bool,
+}
+
+impl Locale {
+    pub const EN_US: Locale = Locale {group: ',', decimal: '.', symbol_first: true};
+    pub const DE_DE: Locale = Locale {group: '.', decimal: ',', symbol_first: false};
+    pub const FR_FR: Locale = Locale {group: '\u{202f}', decimal: ',', symbol_first: false};
+}
+
+impl Default for Locale {
+    fn default() -> Self {
+        Locale::EN_US
+    }
+}
+
+/// An exact amount of a [`Currency`], stored as a count of its minor units.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub struct Money {
+    minor: i64,
+    currency: Currency,
+}
+
+impl Money {
+    /// An amount of `minor` units, such as cents for [`Currency::USD`] or satoshis for [`Currency::BTC`].
+    pub fn new(minor: i64, currency: Currency) -> Self {
+        Money {minor, currency}
+    }
+
+    pub fn zero(currency: Currency) -> Self {
+        Money::new(0, currency)
+    }
+
+    /// Parses an amount written in major units for the default [`Locale`], such as `"$1,234.56"` or `"0.00012 BTC"`.
+    /// Returns `None` if it is not a number or has more decimal places than the currency allows.
+    pub fn parse(value: &str, currency: Currency) -> Option<Self> {
+        Money::parse_in(value, currency, &Locale::default())
+    }
+
+    /// Parses an amount written for `locale`, such as `"1.234,56 €"` for [`Locale::DE_DE`].
+    ///
+    /// The currency's symbol or code may come before or after the number, and the number may only use the
+    /// locale's separators, with digits grouped in threes. Anything else returns `None`.
+    pub fn parse_in(value: &str, currency: Currency, locale: &Locale) -> Option<Self> {
+        let mut rest = value.trim();
+        let mut negative = false;
+        if let Some(unsigned) = rest.strip_prefix('-') { (negative, rest) = (true, unsigned.trim_start()); }
+        rest = currency.strip(rest, true).trim_start();
+        if let Some(unsigned) = rest.strip_prefix('-').filter(|_| !negative) { (negative, rest) = (true, unsigned); }
+        rest = currency.strip(rest, false).trim_end();
+
+        let (whole, fraction) = rest.split_once(locale.decimal).unwrap_or((rest, ""));
+        if fraction.len() > currency.decimals as usize || !fraction.chars().all(|c| c.is_ascii_digit()) { return None; }
+
+        let groups = whole.split(locale.group).collect::<Vec<_>>();
+        let grouped = groups.len() == 1 || groups.iter().enumerate().all(|(i, g)| if i == 0 {(1..=3).contains(&g.len())} else {g.len() == 3});
+        let whole = groups.concat();
+        if !grouped || !whole.chars().all(|c| c.is_ascii_digit()) || (whole.is_empty() && fraction.is_empty()) { return None; }
+
+        let whole = if whole.is_empty() {0} else {whole.parse::<i128>().ok()?};
+        let fraction = format!("{fraction:0<width$}", width = currency.decimals as usize);
+        let fraction = if fraction.is_empty() {0} else {fraction.parse::<i128>().ok()?};
+        let minor = i64::try_from(whole * currency.scale() + fraction).ok()?;
+        Some(Money::new(if negative {-minor} else {minor}, currency))
+    }
+
+    pub fn minor(&self) -> i64 {
+        self.minor
+    }
+
+    pub fn currency(&self) -> Currency {
+        self.currency
+    }
+
+    pub fn is_negative(&self) -> bool {
+        self.minor < 0
+    }
+
+    /// The amount in major units. Only for display and charts; do arithmetic on [`Money`] itself.
+    pub fn to_f64(&self) -> f64 {
+        self.minor as f64 / self.currency.scale() as f64
+    }
+
+    /// Adds `other`, or returns `None` if the currencies differ or the sum overflows.
+    pub fn checked_add(&self, other: Money) -> Option<Money> {
+        (self.currency == other.currency).then_some(())?;
+        Some(Money::new(self.minor.checked_add(other.minor)?, self.currency))
+    }
+
+    /// Subtracts `other`, or returns `None` if the currencies differ or the difference overflows.
+    pub fn checked_sub(&self, other: Money) -> Option<Money> {
+        (self.currency == other.currency).then_some(())?;
+        Some(Money::new(self.minor.checked_sub(other.minor)?, self.currency))
+    }
+
+    /// Converts into `to`, where `price` is what one whole unit of `to` costs in this amount's currency.
+    /// Rounds half away from zero to the nearest minor unit of `to`.
+    pub fn convert(&self, to: Currency, price: Money) -> Option<Money> {
+        if price.currency != self.currency || price.minor == 0 { return None; }
+        let numerator = self.minor as i128 * to.scale();
+        let denominator = price.minor as i128;
+        let rounded = (2 * numerator + numerator.signum() * denominator.abs()) / (2 * denominator);
+        Some(Money::new(i64::try_from(rounded).ok()?, to))
+    }
+
+    /// The amount as it would be typed into an input, without symbol, grouping or trailing zeros, such as `"1234.5"`.
+    pub fn format_plain(&self, locale: &Locale) -> String {
+        let scale = self.currency.scale() as u128;
+        let abs = (self.minor as i128).unsigned_abs();
+        let sign = if self.is_negative() {"-"} else {""};
+        let fraction = format!("{:0width$}", abs % scale, width = self.currency.decimals as usize);
+        match fraction.trim_end_matches('0') {
+            "" => format!("{sign}{}", abs / scale),
+            fraction => format!("{sign}{}{}{fraction}", abs / scale, locale.decimal),
+        }
+    }
+
+    pub fn format(&self, locale: &Locale) -> String {
+        let scale = self.currency.scale() as u128;
+        let abs = (self.minor as i128).unsigned_abs();
+        let whole = (abs / scale).to_string();
+
+        let mut grouped = String::new();
+        for (i, c) in whole.chars().enumerate() {
+            if i > 0 && (whole.len() - i) % 3 == 0 { grouped.push(locale.group); }
+            grouped.push(c);
+        }
+
+        if self.currency.decimals > 0 {
+            grouped.push(locale.decimal);
+            grouped.push_str(&format!("{:0width$}", abs % scale, width = self.currency.decimals as usize));
+        }
+
+        let sign = if self.is_negative() {"-"} else {""};
+        match self.currency.symbol {
+            Some(symbol) if locale.symbol_first => format!("{sign}{symbol}{grouped}"),
+            Some(symbol) => format!("{sign}{grouped} {symbol}"),
+            None => format!("{sign}{grouped} {}", self.currency.code),
+        }
+    }
+}
+
+impl fmt::Display for Money {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.format(&Locale::default()))
+    }
+}
+
+/// What one whole unit of `base` costs in another currency, as of a point in time.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Rate {
+    pub base: Currency,
+    pub price: Money,
+    pub as_of: DateTime<Utc>,
+}
+
+impl Rate {
+    /// How old a rate can get before amounts converted with it are marked as out of date.
+    pub const STALE_AFTER: Duration = Duration::minutes(10);
+
+    pub fn new(base: Currency, price: Money, as_of: DateTime<Utc>) -> Self {
+        Rate {base, price, as_of}
+    }
+
+    /// Converts `amount` from `base` into the price's currency or back, or returns `None` if it is in neither.
+    pub fn convert(&self, amount: Money) -> Option<Money> {
+        if amount.currency() == self.price.currency() { return amount.convert(self.base, self.price); }
+        if amount.currency() != self.base { return None; }
+
+        let numerator = amount.minor() as i128 * self.price.minor() as i128;
+        let denominator = self.base.scale();
+        let rounded = (2 * numerator + numerator.signum() * denominator) / (2 * denominator);
+        Some(Money::new(i64::try_from(rounded).ok()?, self.price.currency()))
+    }
+
+    pub fn is_stale(&self) -> bool {
+        Utc::now() - self.as_of > Rate::STALE_AFTER
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_accepts_symbols_codes_and_separators() {
+        assert_eq!(Money::parse("$1,234.56", Currency::USD), Some(Money::new(123_456, Currency::USD)));
+        assert_eq!(Money::parse("1234.5 USD", Currency::USD), Some(Money::new(123_450, Currency::USD)));
+        assert_eq!(Money::parse("-$5", Currency::USD), Some(Money::new(-500, Currency::USD)));
+        assert_eq!(Money::parse(".5", Currency::USD), Some(Money::new(50, Currency::USD)));
+        assert_eq!(Money::parse("0.00012 BTC", Currency::BTC), Some(Money::new(12_000, Currency::BTC)));
+    }
+
+    #[test]
+    fn parse_rejects_junk() {
+        assert_eq!(Money::parse("12abc34", Currency::USD), None);
+        assert_eq!(Money::parse("1.234", Currency::USD), None);
+        assert_eq!(Money::parse("1,23.45", Currency::USD), None);
+        assert_eq!(Money::parse("€5", Currency::USD), None);
+        assert_eq!(Money::parse("", Currency::USD), None);
+        assert_eq!(Money::parse("$", Currency::USD), None);
+        assert_eq!(Money::parse("5-", Currency::USD), None);
+    }
+
+    #[test]
+    fn parse_follows_locale() {
+        assert_eq!(Money::parse_in("1.234,56", Currency::USD, &Locale::DE_DE), Some(Money::new(123_456, Currency::USD)));
+        assert_eq!(Money::parse_in("1.234,56 €", Currency::EUR, &Locale::DE_DE), Some(Money::new(123_456, Currency::EUR)));
+        assert_eq!(Money::parse_in("1\u{202f}234,5", Currency::EUR, &Locale::FR_FR), Some(Money::new(123_450, Currency::EUR)));
+        assert_eq!(Money::parse_in("1,234.56", Currency::USD, &Locale::DE_DE), None);
+    }
+
+    #[test]
+    fn format_each_locale() {
+        let amount = Money::new(-123_456_789, Currency::EUR);
+        assert_eq!(amount.format(&Locale::EN_US), "-€1,234,567.89");
+        assert_eq!(amount.format(&Locale::DE_DE), "-1.234.567,89 €");
+        assert_eq!(amount.format(&Locale::FR_FR), "-1\u{202f}234\u{202f}567,89 €");
+        assert_eq!(Money::new(12_000, Currency::BTC).format(&Locale::EN_US), "0.00012000 BTC");
+        assert_eq!(Money::new(1_500, Currency::JPY).format(&Locale::EN_US), "¥1,500");
+    }
+
+    #[test]
+    fn format_plain_round_trips() {
+        assert_eq!(Money::new(123_450, Currency::USD).format_plain(&Locale::EN_US), "1234.5");
+        assert_eq!(Money::new(10_000, Currency::USD).format_plain(&Locale::EN_US), "100");
+        assert_eq!(Money::new(-156_250, Currency::BTC).format_plain(&Locale::DE_DE), "-0,0015625");
+        let amount = Money::new(123_456, Currency::EUR);
+        assert_eq!(Money::parse_in(&amount.format_plain(&Locale::FR_FR), Currency::EUR, &Locale::FR_FR), Some(amount));
+    }
+
+    #[test]
+    fn convert_rounds_half_away_from_zero() {
+        let price = Money::new(300, Currency::USD);
+        // 1 cent at $3 per unit of a 2 decimal currency is 0.333.. units, 2 cents is 0.666.. units.
+        assert_eq!(Money::new(1, Currency::USD).convert(Currency::EUR, price), Some(Money::new(0, Currency::EUR)));
+        assert_eq!(Money::new(2, Currency::USD).convert(Currency::EUR, price), Some(Money::new(1, Currency::EUR)));
+        assert_eq!(Money::new(-2, Currency::USD).convert(Currency::EUR, price), Some(Money::new(-1, Currency::EUR)));
+
+        let half = Money::new(200, Currency::USD);
+        assert_eq!(Money::new(1, Currency::USD).convert(Currency::EUR, half), Some(Money::new(1, Currency::EUR)));
+        assert_eq!(Money::new(-1, Currency::USD).convert(Currency::EUR, half), Some(Money::new(-1, Currency::EUR)));
+        assert_eq!(Money::new(1, Currency::EUR).convert(Currency::EUR, half), None);
+    }
+
+    #[test]
+    fn rate_converts_both_ways() {
+        let rate = Rate::new(Currency::BTC, Money::new(10_000_000, Currency::USD), Utc::now());
+        assert_eq!(rate.convert(Money::new(100_000, Currency::USD)), Some(Money::new(1_000_000, Currency::BTC)));
+        assert_eq!(rate.convert(Money::new(1_000_000, Currency::BTC)), Some(Money::new(100_000, Currency::USD)));
+        assert_eq!(rate.convert(Money::new(-1_000_000, Currency::BTC)), Some(Money::new(-100_000, Currency::USD)));
+        assert_eq!(rate.convert(Money::new(100, Currency::EUR)), None);
+        assert!(!rate.is_stale());
+    }
+}
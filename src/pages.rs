@@ -1,19 +1,24 @@
-use pelican_ui::{Context, Component};
+use pelican_ui::{Context, Component, State};
 use pelican_ui::drawable::{Drawable, Align};
 use pelican_ui::layouts::{Offset, Stack};
-use pelican_ui::events::{OnEvent, Event};
-use pelican_ui::components::avatar::{AvatarContent, AvatarIconStyle};
+use pelican_ui::events::{OnEvent, Event, TickEvent};
+use pelican_ui::components::avatar::AvatarContent;
 use pelican_ui::components::interface::navigation::AppPage as PelicanAppPage;
 use pelican_ui::components::interface::general::{Header, Bumper as PelicanBumper, Content, Page as PelicanPage};
 use pelican_ui::utils::Callback;
 use pelican_ui::components::text::{TextStyle, TextSize};
 
 use crate::{Action, Input, Display, FnMutClone, NavFn, ValidityFn};
-use crate::flow::Flow;
+use crate::flow::{Flow, PageBuilder, ActiveFlows};
+use crate::validators;
+use crate::components::Toast;
+
+use std::collections::HashMap;
+
 #[derive(Clone)]
 pub enum PageType {
     Display {title: String, items: Vec<Display>, branch: Option<(String, Flow)>, bumper: Bumper, offset: Offset, flow_length: usize, next: Option<NavFn>},
-    Input {title: String, items: Input, bumper: Bumper, flow_length: usize, next: Option<NavFn>},
+    Input {title: String, items: Vec<Input>, bumper: Bumper, flow_length: usize, next: Option<NavFn>},
     Settings {title: String, items: Vec<Input>, bumper: Bumper, flow_length: usize, next: Option<NavFn>},
 }
 
@@ -36,20 +41,62 @@ impl PageType {
         }
     }
 
+    /// A success page with an illustration from the app's assets, such as `brand/illustrations/toucan.svg`, instead of an icon.
+    pub fn success_with_illustration(title: &str, path: &str, text: &str) -> Self {
+        PageType::Display {
+            title: title.to_string(),
+            items: vec![Display::illustration(path), Display::Text {text: text.to_string(), size: TextSize::H4, style: TextStyle::Heading, align: Align::Center}],
+            branch: None,
+            bumper: Bumper::Done,
+            offset: Offset::Center,
+            flow_length: 1,
+            next: None,
+        }
+    }
+
+    pub fn loading(title: &str, text: &str) -> Self {
+        PageType::Display {
+            title: title.to_string(),
+            items: vec![Display::Text {text: text.to_string(), size: TextSize::H4, style: TextStyle::Heading, align: Align::Center}],
+            branch: None,
+            bumper: Bumper::Locked,
+            offset: Offset::Center,
+            flow_length: 1,
+            next: None,
+        }
+    }
+
+    pub fn failure(title: &str, text: &str, retry: Action) -> Self {
+        PageType::Display {
+            title: title.to_string(),
+            items: vec![Display::illustration("brand/error.svg"), Display::instructions(text)],
+            branch: None,
+            bumper: Bumper::custom("Try again", retry),
+            offset: Offset::Center,
+            flow_length: 1,
+            next: None,
+        }
+    }
+
     pub fn review(title: &str, items: Vec<Display>) -> Self {
         PageType::Display { title: title.to_string(), items, branch: None, bumper: Bumper::default(), offset: Offset::Start, flow_length: 1, next: None}
     }
 
-    pub fn input(title: &str, items: Input, bumper: Bumper) -> Self {
-        PageType::Input { title: title.to_string(), items, bumper,flow_length: 1, next: None }
+    pub fn input(title: &str, item: Input, bumper: Bumper) -> Self {
+        PageType::Input { title: title.to_string(), items: vec![item], bumper, flow_length: 1, next: None }
+    }
+
+    /// A page with several inputs of any kind. The bumper stays disabled until every input is valid.
+    pub fn inputs(title: &str, items: Vec<Input>, bumper: Bumper) -> Self {
+        PageType::Input { title: title.to_string(), items, bumper, flow_length: 1, next: None }
     }
 
     pub fn display(title: &str, items: Vec<Display>, branch: Option<(String, Flow)>, bumper: Bumper, offset: Offset) -> Self {
         PageType::Display { title: title.to_string(), items, branch, bumper, offset, flow_length: 1, next: None}
     }
 
-    pub fn settings(title: &str, avatar: AvatarContent, text_fields: Vec<(String, String, Box<dyn ValidityFn>)>, bumper: Bumper) -> Self {
-        let mut items = vec![Input::avatar(avatar, Some(("edit".to_string(), AvatarIconStyle::Secondary)), Some(Action::SelectImage))];
+    pub fn settings(title: &str, avatar: AvatarContent, avatar_tag: &str, text_fields: Vec<(String, String, Box<dyn ValidityFn>)>, bumper: Bumper) -> Self {
+        let mut items = vec![Input::avatar_picker(avatar, avatar_tag)];
         text_fields.into_iter().for_each(|(i, t, c)| items.push(Input::text(&i, None, &t, c)));
         
         PageType::Settings { 
@@ -61,6 +108,11 @@ impl PageType {
         }
     }
 
+    /// A settings page made of any inputs, such as toggles, segmented controls and navigation rows.
+    pub fn preferences(title: &str, items: Vec<Input>, bumper: Bumper) -> Self {
+        PageType::Settings {title: title.to_string(), items, bumper, flow_length: 1, next: None}
+    }
+
     pub fn name(&self) -> String {
         match self {
             PageType::Settings {title, ..} |
@@ -69,6 +121,15 @@ impl PageType {
         }
     }
 
+    /// The inputs on this page, which are cleared and wiped when its flow ends.
+    pub fn page_inputs(&self) -> Vec<Input> {
+        match self {
+            PageType::Display {..} => Vec::new(),
+            PageType::Input {items, ..} |
+            PageType::Settings {items, ..} => items.clone(),
+        }
+    }
+
     pub fn flow_length(&mut self) -> &mut usize {
         match self {
             PageType::Settings {flow_length, ..} |
@@ -93,15 +154,15 @@ impl BuildablePage for PageType {
 
         let (offset, content, header_icon, validity_fn) = match self {
             PageType::Display {items, offset, branch, ..} => (*offset, items.iter_mut().filter_map(|di| di.build(ctx)).flatten().collect::<Vec<Box<dyn Drawable>>>(), branch.take(), None),
-            PageType::Input {items, ..} => (Offset::Start, items.build(ctx).unwrap_or_default(), None, items.check()),
+            PageType::Input {items, ..} |
             PageType::Settings {items, ..} => {
                 let checks = items.iter_mut().filter_map(|item| item.check()).collect::<Vec<_>>();
-                let check = Box::new(move |ctx: &mut Context| checks.clone().iter_mut().all(|check| (check)(ctx))) as Box<dyn ValidityFn>;
+                let check = Box::new(validators::all(checks)) as Box<dyn ValidityFn>;
                 (Offset::Start, items.iter_mut().filter_map(|di| di.build(ctx)).flatten().collect::<Vec<Box<dyn Drawable>>>(), None, Some(check))
             }
         };
 
-        let validity_fn = validity_fn.map(|mut vfn| Box::new(move |ctx: &mut Context| (vfn)(ctx)) as Box<dyn FnMut(&mut Context) -> bool + 'static>);
+        let validity_fn = validity_fn.map(|mut vfn| Box::new(move |ctx: &mut Context| (vfn)(ctx).is_err()) as Box<dyn FnMut(&mut Context) -> bool + 'static>);
 
         let bumper = match self {
             PageType::Display {bumper, ..} => bumper,
@@ -127,22 +188,76 @@ impl BuildablePage for PageType {
                     let header = Header::stack(ctx, &self.name(), icon);
                     (header, Some(bumper))
                 }
-                None => (Header::stack_end(ctx, &self.name()), Some(PelicanBumper::stack_end(ctx, Some(flow_len))))
+                None => (Header::stack_end(ctx, &self.name()), Some(done(ctx, flow_len)))
             },
-            Bumper::Done => (Header::stack_end(ctx, &self.name()), Some(PelicanBumper::stack_end(ctx, Some(flow_len)))),
-            Bumper::None => (Header::stack(ctx, &self.name(), icon), None)
+            Bumper::Done => (Header::stack_end(ctx, &self.name()), Some(done(ctx, flow_len))),
+            Bumper::None => (Header::stack(ctx, &self.name(), icon), None),
+            Bumper::Locked => (Header::home(ctx, &self.name(), None), None),
         };
 
         AppPage::new(header, Content::new(ctx, offset, content), bumper, self.clone())
     }
 }
 
+/// The bumper on the last page of a flow, which closes the flow without counting as the user going back.
+fn done(ctx: &mut Context, flow_len: usize) -> PelicanBumper {
+    PelicanBumper::stack(ctx, Some("Done"), false, Box::new(move |ctx: &mut Context| ActiveFlows::done(ctx, flow_len)), None, None)
+}
+
+/// A page of a [`Flow`], kept around so it can be rebuilt from its [`PageBuilder`] when its dependencies change.
+#[derive(Clone)]
+pub(crate) struct FlowPage {
+    builder: Box<dyn PageBuilder>,
+    flow_length: usize,
+    next: Option<NavFn>,
+    dependencies: Vec<Dependency>,
+    inputs: Vec<Input>,
+}
+
+impl FlowPage {
+    pub fn new(builder: Box<dyn PageBuilder>, flow_length: usize, next: Option<NavFn>, mut dependencies: Vec<Dependency>) -> Self {
+        dependencies.push(Dependency::on::<Revision>());
+        FlowPage { builder, flow_length, next, dependencies, inputs: Vec::new() }
+    }
+
+    /// The inputs shown the last time this page was built.
+    pub fn inputs(&self) -> Vec<Input> {
+        self.inputs.clone()
+    }
+}
+
+impl std::fmt::Debug for FlowPage {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "FlowPage")
+    }
+}
+
+impl BuildablePage for FlowPage {
+    fn build(&mut self, ctx: &mut Context) -> AppPage {
+        let mut page = (self.builder)(ctx.state());
+        *page.flow_length() = self.flow_length;
+        *page.get_nav() = self.next.clone();
+        self.dependencies.iter_mut().for_each(|d| { d.changed(ctx.state()); });
+        self.inputs = page.page_inputs();
+
+        let mut app_page = page.build(ctx);
+        app_page.3 = Box::new(self.clone());
+        app_page
+    }
+
+    fn is_stale(&mut self, ctx: &mut Context) -> bool {
+        self.dependencies.iter_mut().fold(false, |stale, d| d.changed(ctx.state()) || stale)
+    }
+}
+
 #[derive(Debug, Clone)]
 pub struct RootPage {
     pub title: String,
     pub content: Vec<Display>,
     pub header_icon: Option<(String, Box<dyn FnMutClone>)>,
     pub bumper: (RootBumper, Option<RootBumper>),
+    pub dependencies: Vec<Dependency>,
+    builder: Option<Box<dyn ContentBuilder>>,
 }
 
 impl RootPage {
@@ -151,17 +266,48 @@ impl RootPage {
             title: title.to_string(),
             content,
             header_icon,
-            bumper: (bumper_a, bumper_b)
+            bumper: (bumper_a, bumper_b),
+            dependencies: Vec::new(),
+            builder: None,
+        }
+    }
+
+    /// A root page whose content is built from [`State`], and rebuilt whenever one of its `dependencies` changes.
+    pub fn dynamic(
+        title: &str,
+        content: impl FnMut(&mut State) -> Vec<Display> + Clone + 'static,
+        dependencies: Vec<Dependency>,
+        header_icon: Option<(String, Box<dyn FnMutClone>)>,
+        bumper_a: RootBumper,
+        bumper_b: Option<RootBumper>
+    ) -> Self {
+        RootPage {
+            title: title.to_string(),
+            content: Vec::new(),
+            header_icon,
+            bumper: (bumper_a, bumper_b),
+            dependencies,
+            builder: Some(Box::new(content)),
         }
     }
 }
 
 pub trait BuildablePage: std::fmt::Debug {
     fn build(&mut self, ctx: &mut Context) -> AppPage;
+
+    /// Whether the state this page was built from has changed since it was last built.
+    fn is_stale(&mut self, _ctx: &mut Context) -> bool { false }
 }
 
 impl BuildablePage for RootPage {
+    fn is_stale(&mut self, ctx: &mut Context) -> bool {
+        self.dependencies.iter_mut().fold(false, |stale, d| d.changed(ctx.state()) || stale)
+    }
+
     fn build(&mut self, ctx: &mut Context) -> AppPage {
+        if let Some(builder) = self.builder.as_mut() { self.content = (builder)(ctx.state()); }
+        self.dependencies.iter_mut().for_each(|d| { d.changed(ctx.state()); });
+
         let header_icon = self.header_icon.as_ref().map(|(s, c)| {
             let closure = c.clone_box();
             (s.to_string(), Box::new(move |ctx: &mut Context| (closure.clone_box())(ctx)) as Callback) 
@@ -184,12 +330,12 @@ impl BuildablePage for RootPage {
 }
 
 #[derive(Component, Debug)]
-pub struct AppPage(Stack, pub PelicanPage, #[skip] Box<dyn BuildablePage>);
+pub struct AppPage(Stack, pub PelicanPage, Toast, #[skip] Box<dyn BuildablePage>);
 impl OnEvent for AppPage {
-    fn on_event(&mut self, _ctx: &mut Context, event: Box<dyn Event>) -> Vec<Box<dyn Event>> {
-        // if event.downcast_ref::<StateChangedEvent>().is_some() {
-        //     *self = self.2.build(ctx);
-        // }
+    fn on_event(&mut self, ctx: &mut Context, event: Box<dyn Event>) -> Vec<Box<dyn Event>> {
+        if event.downcast_ref::<TickEvent>().is_some() && self.3.is_stale(ctx) {
+            *self = self.3.build(ctx);
+        }
 
         vec![event]
     }
@@ -199,7 +345,7 @@ impl PelicanAppPage for AppPage {}
 
 impl AppPage {
     pub fn new(header: Header, content: Content, bumper: Option<PelicanBumper>, page: impl BuildablePage + 'static) -> Self {
-        AppPage(Stack::default(), PelicanPage::new(header, content, bumper), Box::new(page))
+        AppPage(Stack::default(), PelicanPage::new(header, content, bumper), Toast::default(), Box::new(page))
     }
 }
 
@@ -224,6 +370,8 @@ pub enum Bumper {
     Custom { label: String, action: Action, secondary: Option<(String, Action)> },
     Done,
     None,
+    /// No bumper and no back button, for pages the user has to wait on such as [`PageType::loading`].
+    Locked,
 }
 
 impl Bumper {
@@ -234,4 +382,100 @@ impl Bumper {
     pub fn double(l1: &str, a1: Action, l2: &str, a2: Action) -> Self {
         Bumper::Custom {label: l1.to_string(), action: a1, secondary: Some((l2.to_string(), a2))}
     }
-}
\ No newline at end of file
+}
+/// Touched by [`refresh`] to rebuild the flow pages currently on screen.
+struct Revision;
+
+/// Rebuilds the flow pages currently on screen, e.g. after writing a new value into an input's tag.
+pub fn refresh(ctx: &mut Context) {
+    Dependency::touch::<Revision>(ctx.state());
+}
+
+/// How many times each [`Dependency`] key has been touched.
+#[derive(Debug, Clone, Default)]
+struct Versions(HashMap<String, u64>);
+
+/// A piece of [`State`] a page is built from.
+///
+/// Pages only rebuild when the value is marked as changed, so checking costs the same however large the value is.
+/// Write through [`Dependency::set`], [`Dependency::set_named`] or [`Dependency::update`] to store a value and mark it
+/// in one go, as [`Form`](crate::Form)s and chk's own inputs do. A plain `State::set`, `set_named` or `get_mut` does
+/// not rebuild anything until [`Dependency::touch`] or [`Dependency::touch_named`] is called.
+#[derive(Debug, Clone)]
+pub struct Dependency {
+    key: String,
+    version: Option<u64>,
+}
+
+impl Dependency {
+    /// Depends on the value of type `T` stored with `State::set`.
+    pub fn on<T: 'static>() -> Self {
+        Dependency {key: std::any::type_name::<T>().to_string(), version: None}
+    }
+
+    /// Depends on the value stored under `tag`, such as an input's value.
+    pub fn named(tag: &str) -> Self {
+        Dependency {key: format!("#{tag}"), version: None}
+    }
+
+    /// Stores `value` and rebuilds the pages that depend on its type.
+    pub fn set<T: 'static>(state: &mut State, value: T) {
+        state.set(value);
+        Self::touch::<T>(state);
+    }
+
+    /// Stores `value` under `tag` and rebuilds the pages that depend on it.
+    pub fn set_named<T: 'static>(state: &mut State, tag: &str, value: T) {
+        state.set_named(tag.to_string(), value);
+        Self::touch_named(state, tag);
+    }
+
+    /// Changes the stored value of type `T` in place and rebuilds the pages that depend on it.
+    pub fn update<T: Default + 'static>(state: &mut State, change: impl FnOnce(&mut T)) {
+        change(state.get_or_default::<T>());
+        Self::touch::<T>(state);
+    }
+
+    /// Marks the value of type `T` as changed, rebuilding the pages that depend on it.
+    pub fn touch<T: 'static>(state: &mut State) {
+        Self::bump(state, Self::on::<T>().key);
+    }
+
+    /// Marks the value stored under `tag` as changed, rebuilding the pages that depend on it.
+    pub fn touch_named(state: &mut State, tag: &str) {
+        Self::bump(state, Self::named(tag).key);
+    }
+
+    fn bump(state: &mut State, key: String) {
+        *state.get_or_default::<Versions>().0.entry(key).or_default() += 1;
+    }
+
+    pub(crate) fn changed(&mut self, state: &mut State) -> bool {
+        let current = state.get_or_default::<Versions>().0.get(&self.key).copied();
+        let changed = current != self.version;
+        self.version = current;
+        changed
+    }
+}
+
+pub trait ContentBuilder: FnMut(&mut State) -> Vec<Display> + 'static {
+    fn clone_box(&self) -> Box<dyn ContentBuilder>;
+}
+
+impl<F> ContentBuilder for F where F: FnMut(&mut State) -> Vec<Display> + Clone + 'static {
+    fn clone_box(&self) -> Box<dyn ContentBuilder> {
+        Box::new(self.clone())
+    }
+}
+
+impl Clone for Box<dyn ContentBuilder> {
+    fn clone(&self) -> Self {
+        self.as_ref().clone_box()
+    }
+}
+
+impl std::fmt::Debug for dyn ContentBuilder {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Content Builder...")
+    }
+}
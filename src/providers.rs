@@ -0,0 +1,264 @@
+//! Platform services that chk's [`Action`](crate::Action)s rely on.
+//!
+//! The host app registers its implementations from [`Application::start`](crate::Application::start),
+//! for example `Providers::set_share(ctx, MyShareSheet)`.
+
+use pelican_ui::{Context, State};
+use chrono::Utc;
+use image::RgbaImage;
+
+use crate::money::{Money, Currency, Rate};
+#[cfg(target_os = "linux")]
+use crate::{Severity, components::toast};
+
+use std::cell::RefCell;
+use std::path::PathBuf;
+use std::rc::Rc;
+
+/// Hands data over to the platform's share sheet.
+pub trait ShareProvider {
+    fn share(&mut self, ctx: &mut Context, data: &str);
+}
+
+/// Lets the user pick an image, returning its encoded bytes (PNG, JPEG, ...) or `None` if they cancelled.
+pub trait ImagePicker {
+    fn pick(&mut self, ctx: &mut Context) -> Option<Vec<u8>>;
+}
+
+/// Reads text from the platform clipboard.
+pub trait ClipboardProvider {
+    fn paste(&mut self, ctx: &mut Context) -> Option<String>;
+}
+
+/// Supplies camera frames, for example to scan QR codes.
+pub trait CameraProvider {
+    /// The most recent frame, or `None` while no new frame is available.
+    fn frame(&mut self, ctx: &mut Context) -> Option<RgbaImage>;
+}
+
+/// Supplies exchange rates for converting amounts between currencies.
+pub trait RateProvider {
+    /// The latest known price of one `base` in `quote`, or `None` if there is none.
+    fn rate(&mut self, base: Currency, quote: Currency) -> Option<Rate>;
+}
+
+/// The providers registered by the host app, stored in [`State`](crate::State).
+#[derive(Clone, Default)]
+pub struct Providers {
+    share: Option<Rc<RefCell<dyn ShareProvider>>>,
+    image_picker: Option<Rc<RefCell<dyn ImagePicker>>>,
+    clipboard: Option<Rc<RefCell<dyn ClipboardProvider>>>,
+    camera: Option<Rc<RefCell<dyn CameraProvider>>>,
+    rates: Option<Rc<RefCell<dyn RateProvider>>>,
+}
+
+impl Providers {
+    pub fn set_share(ctx: &mut Context, provider: impl ShareProvider + 'static) {
+        ctx.state().get_or_default::<Providers>().share = Some(Rc::new(RefCell::new(provider)));
+    }
+
+    pub fn set_image_picker(ctx: &mut Context, provider: impl ImagePicker + 'static) {
+        ctx.state().get_or_default::<Providers>().image_picker = Some(Rc::new(RefCell::new(provider)));
+    }
+
+    pub fn set_clipboard(ctx: &mut Context, provider: impl ClipboardProvider + 'static) {
+        ctx.state().get_or_default::<Providers>().clipboard = Some(Rc::new(RefCell::new(provider)));
+    }
+
+    pub fn set_camera(ctx: &mut Context, provider: impl CameraProvider + 'static) {
+        ctx.state().get_or_default::<Providers>().camera = Some(Rc::new(RefCell::new(provider)));
+    }
+
+    pub fn set_rates(ctx: &mut Context, provider: impl RateProvider + 'static) {
+        ctx.state().get_or_default::<Providers>().rates = Some(Rc::new(RefCell::new(provider)));
+    }
+
+    /// Converts `amount` into `to` with the registered [`RateProvider`], returning the rate used alongside it.
+    pub fn convert(state: &mut State, amount: Money, to: Currency) -> Option<(Money, Rate)> {
+        let from = amount.currency();
+        if from == to { return None; }
+        let rates = state.get_or_default::<Providers>().rates.clone()?;
+        let mut rates = rates.borrow_mut();
+        let rate = rates.rate(to, from).or_else(|| rates.rate(from, to))?;
+        Some((rate.convert(amount)?, rate))
+    }
+
+    pub(crate) fn share(ctx: &mut Context) -> Option<Rc<RefCell<dyn ShareProvider>>> {
+        ctx.state().get_or_default::<Providers>().share.clone().or_else(default_share)
+    }
+
+    pub(crate) fn image_picker(ctx: &mut Context) -> Option<Rc<RefCell<dyn ImagePicker>>> {
+        ctx.state().get_or_default::<Providers>().image_picker.clone()
+    }
+
+    pub(crate) fn clipboard(ctx: &mut Context) -> Option<Rc<RefCell<dyn ClipboardProvider>>> {
+        ctx.state().get_or_default::<Providers>().clipboard.clone().or_else(default_clipboard)
+    }
+
+    pub(crate) fn camera(ctx: &mut Context) -> Option<Rc<RefCell<dyn CameraProvider>>> {
+        ctx.state().get_or_default::<Providers>().camera.clone()
+    }
+}
+
+impl std::fmt::Debug for Providers {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Providers")
+    }
+}
+
+#[cfg(target_os = "linux")]
+fn default_share() -> Option<Rc<RefCell<dyn ShareProvider>>> {
+    Some(Rc::new(RefCell::new(ClipboardShare)))
+}
+
+#[cfg(not(target_os = "linux"))]
+fn default_share() -> Option<Rc<RefCell<dyn ShareProvider>>> {
+    None
+}
+
+#[cfg(target_os = "linux")]
+fn default_clipboard() -> Option<Rc<RefCell<dyn ClipboardProvider>>> {
+    Some(Rc::new(RefCell::new(SystemClipboard)))
+}
+
+#[cfg(not(target_os = "linux"))]
+fn default_clipboard() -> Option<Rc<RefCell<dyn ClipboardProvider>>> {
+    None
+}
+
+/// Reads the desktop clipboard. Used on desktop Linux when no provider is registered.
+#[cfg(target_os = "linux")]
+#[derive(Debug, Clone, Default)]
+pub struct SystemClipboard;
+
+#[cfg(target_os = "linux")]
+impl ClipboardProvider for SystemClipboard {
+    fn paste(&mut self, _ctx: &mut Context) -> Option<String> {
+        cli_clipboard::get_contents().map_err(|e| println!("Could not read the clipboard: {e}")).ok()
+    }
+}
+
+/// Shares by copying the data to the clipboard and saying so in a toast. Used on desktop Linux when no provider is registered.
+#[cfg(target_os = "linux")]
+#[derive(Debug, Clone, Default)]
+pub struct ClipboardShare;
+
+#[cfg(target_os = "linux")]
+impl ShareProvider for ClipboardShare {
+    fn share(&mut self, ctx: &mut Context, data: &str) {
+        match cli_clipboard::set_contents(data.to_string()) {
+            Ok(()) => toast(ctx, Severity::Success, "Copied to clipboard"),
+            Err(e) => {
+                println!("Could not copy shared data to the clipboard: {e}");
+                toast(ctx, Severity::Error, "Couldn't copy to the clipboard.");
+            }
+        }
+    }
+}
+
+/// Records everything shared instead of showing a share sheet. Clones share the same record.
+#[derive(Debug, Clone, Default)]
+pub struct RecordingShare(Rc<RefCell<Vec<String>>>);
+
+impl RecordingShare {
+    pub fn shared(&self) -> Vec<String> {
+        self.0.borrow().clone()
+    }
+}
+
+impl ShareProvider for RecordingShare {
+    fn share(&mut self, _ctx: &mut Context, data: &str) {
+        self.0.borrow_mut().push(data.to_string());
+    }
+}
+
+/// Picks the same image file from disk every time, standing in for a native picker.
+#[derive(Debug, Clone)]
+pub struct FileImagePicker(PathBuf);
+
+impl FileImagePicker {
+    pub fn new(path: impl Into<PathBuf>) -> Self {
+        FileImagePicker(path.into())
+    }
+}
+
+impl ImagePicker for FileImagePicker {
+    fn pick(&mut self, _ctx: &mut Context) -> Option<Vec<u8>> {
+        std::fs::read(&self.0).map_err(|e| println!("Could not read {:?}: {e}", self.0)).ok()
+    }
+}
+
+/// Shows the same image file as every camera frame, standing in for a real camera.
+#[derive(Debug, Clone)]
+pub struct FileCamera(PathBuf, Option<RgbaImage>);
+
+impl FileCamera {
+    pub fn new(path: impl Into<PathBuf>) -> Self {
+        FileCamera(path.into(), None)
+    }
+}
+
+impl CameraProvider for FileCamera {
+    fn frame(&mut self, _ctx: &mut Context) -> Option<RgbaImage> {
+        if self.1.is_none() {
+            self.1 = image::open(&self.0).map_err(|e| println!("Could not read {:?}: {e}", self.0)).ok().map(|i| i.to_rgba8());
+        }
+        self.1.clone()
+    }
+}
+
+/// Always answers with the same price, standing in for a live rate feed in tests and offline.
+#[derive(Debug, Clone, Copy)]
+pub struct FixedRate(Currency, Money);
+
+impl FixedRate {
+    /// One `base` always costs `price`.
+    pub fn new(base: Currency, price: Money) -> Self {
+        FixedRate(base, price)
+    }
+}
+
+impl RateProvider for FixedRate {
+    fn rate(&mut self, base: Currency, quote: Currency) -> Option<Rate> {
+        (base == self.0 && quote == self.1.currency()).then(|| Rate::new(self.0, self.1, Utc::now()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Action;
+    use pelican_ui::components::avatar::AvatarContent;
+
+    #[test]
+    fn share_action_uses_registered_provider() {
+        let mut ctx = Context::default();
+        let recorder = RecordingShare::default();
+        Providers::set_share(&mut ctx, recorder.clone());
+
+        (Action::share("addr").get())(&mut ctx);
+
+        assert_eq!(recorder.shared(), vec!["addr".to_string()]);
+    }
+
+    #[test]
+    fn select_image_stores_a_square_avatar() {
+        let mut ctx = Context::default();
+        let path = std::env::temp_dir().join("chk-select-image-test.png");
+        RgbaImage::from_pixel(300, 200, image::Rgba([0, 255, 0, 255])).save(&path).unwrap();
+        Providers::set_image_picker(&mut ctx, FileImagePicker::new(&path));
+
+        (Action::select_image("AvatarInput").get())(&mut ctx);
+        let _ = std::fs::remove_file(&path);
+
+        let avatar = ctx.state().get_named::<AvatarContent>("AvatarInput");
+        assert!(matches!(avatar, Some(AvatarContent::Image(image)) if image.dimensions() == (512, 512)));
+    }
+
+    #[test]
+    fn select_image_without_a_picker_stores_nothing() {
+        let mut ctx = Context::default();
+        (Action::select_image("AvatarInput").get())(&mut ctx);
+        assert!(ctx.state().get_named::<AvatarContent>("AvatarInput").is_none());
+    }
+}
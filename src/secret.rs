@@ -0,0 +1,72 @@
+/// Text entered into a secure [`Input`](crate::Input), such as a password, PIN or recovery phrase.
+///
+/// It is stored in [`State`](crate::State) as a `Secret` rather than a `String`, never shows up in `Debug`
+/// output, and its own bytes are zeroed when it is dropped. Revealing a password copies it into the text on
+/// screen, and that copy is not zeroed.
+#[derive(Clone, Default, PartialEq, Eq)]
+pub struct Secret(Vec<u8>);
+
+impl Secret {
+    pub fn new(value: &str) -> Self {
+        let mut secret = Secret::default();
+        value.chars().for_each(|c| secret.push(c));
+        secret
+    }
+
+    pub fn expose(&self) -> &str {
+        std::str::from_utf8(&self.0).unwrap_or_default()
+    }
+
+    pub fn len(&self) -> usize {
+        self.expose().chars().count()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    /// The whitespace separated words of a recovery phrase.
+    pub fn words(&self) -> Vec<&str> {
+        self.expose().split_whitespace().collect()
+    }
+
+    pub(crate) fn push(&mut self, c: char) {
+        let mut buffer = [0; 4];
+        let bytes = c.encode_utf8(&mut buffer).as_bytes();
+        if self.0.len() + bytes.len() > self.0.capacity() {
+            // Grow by hand so the old allocation is wiped instead of left behind by `Vec`.
+            let mut grown = Vec::with_capacity((self.0.capacity() * 2).max(64));
+            grown.extend_from_slice(&self.0);
+            wipe(&mut self.0);
+            self.0 = grown;
+        }
+        self.0.extend_from_slice(bytes);
+        buffer.fill(0);
+    }
+
+    pub(crate) fn pop(&mut self) {
+        if let Some(c) = self.expose().chars().last() {
+            let len = self.0.len() - c.len_utf8();
+            self.0[len..].fill(0);
+            self.0.truncate(len);
+        }
+    }
+}
+
+impl Drop for Secret {
+    fn drop(&mut self) {
+        wipe(&mut self.0);
+    }
+}
+
+impl std::fmt::Debug for Secret {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Secret(***)")
+    }
+}
+
+fn wipe(bytes: &mut Vec<u8>) {
+    bytes.fill(0);
+    std::hint::black_box(&bytes);
+    bytes.clear();
+}
@@ -24,6 +24,9 @@ pub struct Project {
     pub name: String,
     pub date: String, 
     pub id: String,
+    pub linked: Vec<String>,
+    /// How long each build took, in seconds.
+    pub builds: Vec<(chrono::DateTime<chrono::Utc>, f64)>,
 }
 
 impl Default for Project {
@@ -33,6 +36,10 @@ impl Default for Project {
             name: "Orange".to_string(),
             date: "11/23/2025".to_string(), 
             id: "projectid200".to_string(),
+            linked: Vec::new(),
+            builds: [412.0, 398.0, 455.0, 371.0, 364.0, 380.0, 342.0].into_iter().enumerate().map(|(days_ago, seconds)| {
+                (chrono::Utc::now() - chrono::Duration::days(days_ago as i64 * 2), seconds)
+            }).collect(),
         }
     }
 }
\ No newline at end of file
@@ -1,89 +1,364 @@
-use pelican_ui::{drawables, Context};
-use pelican_ui::drawable::{Drawable, Align};
+use pelican_ui::{drawables, Context, State};
+use pelican_ui::drawable::{Drawable, Align, Image, ShapeType};
 use pelican_ui::utils::{Callback, TitleSubtitle};
 use pelican_ui::components::list_item::{ListItemSection, ListItemInfoLeft, ListItem as PelicanListItem};
-use pelican_ui::components::{Checkbox, CheckboxList, TextInput, RadioSelector, Icon, DataItem, QRCode, NumericalInput};
+use pelican_ui::components::button::{Button, QuickActions};
+use pelican_ui::components::{Checkbox, CheckboxList, Icon, DataItem, QRCode, NumericalInput};
 use pelican_ui::components::text::{ExpandableText, TextStyle, TextSize};
 use pelican_ui::components::avatar::{Avatar, AvatarSize, AvatarContent, AvatarIconStyle};
 use pelican_ui::plugin::PelicanUI;
 
 use crate::pages::RootPage;
 use crate::flow::Flow;
+use crate::validators::{self, Validation};
+use crate::components::{toast, Banner, Chart, Choices, CurrencyDisplay, ConvertedAmount, DateTimeInput, DateRangeInput, ValidationMessage, TextField, Suggestions, SecureInput, SecureKind, Picker, Scanner, Switch, Segmented};
+use crate::secret::Secret;
+use crate::money::{Money, Currency};
+use crate::dates::{DateRange, DatePreset};
+use crate::chart::ChartKind;
+use crate::suggestions::SuggestionSource;
+use crate::providers::Providers;
+use crate::pages::{refresh, Dependency};
+use crate::flow::ActiveFlows;
+
+use chrono::{DateTime, NaiveDate, NaiveDateTime, Utc};
+
+use regex::Regex;
 
 use std::cell::RefCell;
 use std::rc::Rc;
+use std::path::{Path, PathBuf};
 
 #[derive(Debug, Clone)]
 pub enum Input {
-    Text {label: String, actions: Option<Vec<Action>>, tag: String, check: Box<dyn ValidityFn>},
-    Currency {instructions: String, tag: String, check: Box<dyn ValidityFn>},
+    Text {label: String, placeholder: Option<String>, multiline: bool, max_length: Option<usize>, mask: Option<Mask>, suggestions: Option<Box<dyn SuggestionSource>>, actions: Option<Vec<QuickAction>>, tag: String, check: Box<dyn ValidityFn>},
+    Currency {instructions: String, currency: Currency, convert_to: Option<Currency>, tag: String, check: Box<dyn ValidityFn>},
     Date {instructions: String, tag: String, check: Box<dyn ValidityFn>},
     Time {instructions: String, tag: String, check: Box<dyn ValidityFn>},
-    Enumerator {items: Vec<EnumItem>, tag: String},
-    Avatar {content: AvatarContent, flair: Option<(String, AvatarIconStyle)>, action: Option<Action>},
-    Boolean {items: Vec<ChecklistItem>}
+    Enumerator {items: Vec<EnumItem>, tag: String, default: Option<String>, check: Box<dyn ValidityFn>},
+    Avatar {content: AvatarContent, flair: Option<(String, AvatarIconStyle)>, action: Option<Action>, tag: Option<String>},
+    Boolean {items: Vec<ChecklistItem>, check: Box<dyn ValidityFn>},
+    Secure {kind: SecureKind, label: String, tag: String, check: Box<dyn ValidityFn>},
+    Picker {label: String, items: Vec<ListItem>, multiple: bool, tag: String, check: Box<dyn ValidityFn>},
+    Scan {instructions: String, tag: String, check: Box<dyn ValidityFn>},
+    Toggle {label: String, description: Option<String>, tag: String, default: bool},
+    Segmented {label: String, options: Vec<String>, tag: String, default: String},
+    Navigation {label: String, description: Option<String>, flow: Flow},
+    DateTime {instructions: String, tag: String, check: Box<dyn ValidityFn>},
+    DateRange {instructions: String, presets: Vec<DatePreset>, tag: String, check: Box<dyn ValidityFn>},
 }
 
 impl Input {
-    pub fn currency(instructions: &str, tag: &str, check: impl FnMut(&mut Context) -> bool + Clone + 'static) -> Self {
-        Input::Currency {instructions: instructions.to_string(), tag: tag.to_string(), check: Box::new(check)}
+    pub fn currency(instructions: &str, tag: &str, check: impl FnMut(&mut Context) -> Validation + Clone + 'static) -> Self {
+        Input::Currency {instructions: instructions.to_string(), currency: Currency::USD, convert_to: None, tag: tag.to_string(), check: Box::new(check)}
+    }
+
+    /// A currency input for amounts of `currency` that shows what the amount typed is worth in `to`, converted by the
+    /// registered [`RateProvider`](crate::RateProvider). Tapping swap types in `to` instead; the amount in `currency` is
+    /// still what [`Field::money`](crate::Field::money) and [`validators::money_range`] read.
+    pub fn currency_converted(instructions: &str, currency: Currency, to: Currency, tag: &str, check: impl FnMut(&mut Context) -> Validation + Clone + 'static) -> Self {
+        Input::Currency {instructions: instructions.to_string(), currency, convert_to: Some(to), tag: tag.to_string(), check: Box::new(check)}
     }
 
-    pub fn date(instructions: &str, tag: &str, check: impl FnMut(&mut Context) -> bool + Clone + 'static) -> Self {
+    pub fn date(instructions: &str, tag: &str, check: impl FnMut(&mut Context) -> Validation + Clone + 'static) -> Self {
         Input::Date {instructions: instructions.to_string(), tag: tag.to_string(), check: Box::new(check)}
     }
 
-    pub fn time(instructions: &str, tag: &str, check: impl FnMut(&mut Context) -> bool + Clone + 'static) -> Self {
+    pub fn time(instructions: &str, tag: &str, check: impl FnMut(&mut Context) -> Validation + Clone + 'static) -> Self {
         Input::Time {instructions: instructions.to_string(), tag: tag.to_string(), check: Box::new(check)}
     }
 
     pub fn enumerator(items: Vec<EnumItem>, tag: &str) -> Self {
-        Input::Enumerator {items, tag: tag.to_string()}
+        Input::Enumerator {items, tag: tag.to_string(), default: None, check: Box::new(validators::selected(tag))}
+    }
+
+    /// An enumerator that starts on the item titled `default` when `tag` does not already hold one of its items.
+    pub fn enumerator_with_default(items: Vec<EnumItem>, tag: &str, default: &str) -> Self {
+        Input::Enumerator {items, tag: tag.to_string(), default: Some(default.to_string()), check: Box::new(validators::selected(tag))}
+    }
+
+    pub fn text(label: &str, actions: Option<Vec<QuickAction>>, tag: &str, check: impl FnMut(&mut Context) -> Validation + Clone + 'static) -> Self {
+        Input::Text {label: label.to_string(), placeholder: None, multiline: false, max_length: None, mask: None, suggestions: None, actions, tag: tag.to_string(), check: Box::new(check)}
+    }
+
+    /// A multi-line text field for memos, descriptions and messages, with a live character counter when `max_length` is set.
+    pub fn text_area(label: &str, placeholder: &str, max_length: Option<usize>, tag: &str, check: impl FnMut(&mut Context) -> Validation + Clone + 'static) -> Self {
+        let input = Input::Text {label: label.to_string(), placeholder: Some(placeholder.to_string()), multiline: true, max_length: None, mask: None, suggestions: None, actions: None, tag: tag.to_string(), check: Box::new(check)};
+        match max_length {
+            Some(max) => input.with_max_length(max),
+            None => input,
+        }
+    }
+
+    /// A text field that formats whatever is typed into it with `mask`, such as a phone or card number.
+    pub fn masked(label: &str, mask: Mask, tag: &str, check: impl FnMut(&mut Context) -> Validation + Clone + 'static) -> Self {
+        let check = validators::all(vec![Box::new(validators::mask(tag, mask.clone())), Box::new(check)]);
+        Input::Text {label: label.to_string(), placeholder: Some(mask.pattern().replace('#', "0")), multiline: false, max_length: None, mask: Some(mask), suggestions: None, actions: None, tag: tag.to_string(), check: Box::new(check)}
+    }
+
+    /// Replaces the generated "Enter {label}..." placeholder of a text input. Has no effect on other inputs.
+    pub fn with_placeholder(mut self, text: &str) -> Self {
+        if let Input::Text {placeholder, ..} = &mut self { *placeholder = Some(text.to_string()); }
+        self
     }
 
-    pub fn text(label: &str, actions: Option<Vec<Action>>, tag: &str, check: impl FnMut(&mut Context) -> bool + Clone + 'static) -> Self {
-        Input::Text {label: label.to_string(), actions, tag: tag.to_string(), check: Box::new(check)}
+    /// Lists the candidates from `source` that match what has been typed under a text input. Has no effect on other inputs.
+    pub fn with_suggestions(mut self, source: impl SuggestionSource) -> Self {
+        if let Input::Text {suggestions, ..} = &mut self { *suggestions = Some(Box::new(source)); }
+        self
+    }
+
+    /// Limits a text input to `max` characters, showing a live counter and blocking the page while it is over.
+    /// Has no effect on other inputs.
+    pub fn with_max_length(mut self, max: usize) -> Self {
+        if let Input::Text {max_length, tag, check, ..} = &mut self {
+            *max_length = Some(max);
+            *check = Box::new(validators::all(vec![check.clone(), Box::new(validators::max_length(tag, max))]));
+        }
+        self
     }
 
     pub fn avatar(content: AvatarContent, flair: Option<(String, AvatarIconStyle)>, action: Option<Action>) -> Self {
-        Input::Avatar {content, flair, action}
+        Input::Avatar {content, flair, action, tag: None}
+    }
+
+    /// An editable avatar showing the image picked into `tag`, or `content` until one is picked.
+    pub fn avatar_picker(content: AvatarContent, tag: &str) -> Self {
+        Input::Avatar {
+            content,
+            flair: Some(("edit".to_string(), AvatarIconStyle::Secondary)),
+            action: Some(Action::select_image(tag)),
+            tag: Some(tag.to_string())
+        }
+    }
+
+    /// A list of checkboxes that blocks the page until at least `min_checked` of them are checked.
+    pub fn checklist(items: Vec<ChecklistItem>, min_checked: usize) -> Self {
+        let tags = items.iter().map(|item| item.tag.clone()).collect::<Vec<_>>();
+        Input::Boolean {items, check: Box::new(validators::checked(tags, min_checked))}
+    }
+
+    /// A masked text field. The value is stored under `tag` as a [`Secret`].
+    pub fn password(label: &str, tag: &str, check: impl FnMut(&mut Context) -> Validation + Clone + 'static) -> Self {
+        Input::Secure {kind: SecureKind::Password, label: label.to_string(), tag: tag.to_string(), check: Box::new(check)}
+    }
+
+    /// A keypad for entering exactly `length` digits. The value is stored under `tag` as a [`Secret`].
+    pub fn pin(label: &str, length: usize, tag: &str) -> Self {
+        Input::Secure {kind: SecureKind::Pin(length), label: label.to_string(), tag: tag.to_string(), check: Box::new(validators::secret_length(tag, length, length))}
+    }
+
+    /// A grid of `words` recovery words with suggestions from `wordlist`. The value is stored under `tag` as a [`Secret`].
+    pub fn recovery_phrase(label: &str, words: usize, wordlist: Vec<String>, tag: &str) -> Self {
+        let wordlist = Rc::new(wordlist);
+        Input::Secure {
+            kind: SecureKind::Phrase(words, wordlist.clone()),
+            label: label.to_string(),
+            tag: tag.to_string(),
+            check: Box::new(validators::phrase(tag, words, wordlist))
+        }
+    }
+
+    /// A searchable list that writes the tag of the picked [`ListItem`] to `tag`.
+    pub fn picker(label: &str, items: Vec<ListItem>, tag: &str) -> Self {
+        Input::Picker {label: label.to_string(), items, multiple: false, tag: tag.to_string(), check: Box::new(validators::selected(tag))}
+    }
+
+    /// A searchable list that writes the tags of every picked [`ListItem`] to `tag` as a `Vec<String>`.
+    pub fn multi_picker(label: &str, items: Vec<ListItem>, min_picked: usize, tag: &str) -> Self {
+        Input::Picker {label: label.to_string(), items, multiple: true, tag: tag.to_string(), check: Box::new(validators::picked(tag, min_picked))}
+    }
+
+    /// A camera preview that writes the text of the first QR code it sees to `tag`.
+    /// Frames come from the [`CameraProvider`](crate::CameraProvider) registered in [`Providers`].
+    pub fn scan(instructions: &str, tag: &str) -> Self {
+        Input::Scan {instructions: instructions.to_string(), tag: tag.to_string(), check: Box::new(validators::required(tag))}
     }
 
-    pub fn checklist(items: Vec<ChecklistItem>) -> Self {
-        Input::Boolean {items}
+    /// A date and a time field. Their combined value is stored under `tag` as an `Option<NaiveDateTime>`.
+    pub fn date_time(instructions: &str, tag: &str, min: Option<NaiveDateTime>, max: Option<NaiveDateTime>) -> Self {
+        Input::DateTime {instructions: instructions.to_string(), tag: tag.to_string(), check: Box::new(validators::date_time(tag, min, max))}
+    }
+
+    /// Start and end date fields with `presets` to fill them in one tap. The range is stored under `tag` as an `Option<DateRange>`.
+    pub fn date_range(instructions: &str, presets: Vec<DatePreset>, tag: &str, min: Option<NaiveDate>, max: Option<NaiveDate>) -> Self {
+        Input::DateRange {instructions: instructions.to_string(), presets, tag: tag.to_string(), check: Box::new(validators::date_range(tag, min, max))}
+    }
+
+    /// An on/off switch storing a `bool` under `tag`, starting at `default` when `tag` holds nothing yet.
+    pub fn toggle(label: &str, description: Option<&str>, tag: &str, default: bool) -> Self {
+        Input::Toggle {label: label.to_string(), description: description.map(|d| d.to_string()), tag: tag.to_string(), default}
+    }
+
+    /// A row of options storing the picked one under `tag`, starting at `default` when `tag` holds none of them.
+    pub fn segmented(label: &str, options: Vec<&str>, tag: &str, default: &str) -> Self {
+        Input::Segmented {label: label.to_string(), options: options.into_iter().map(|o| o.to_string()).collect(), tag: tag.to_string(), default: default.to_string()}
+    }
+
+    /// A row that opens `flow` when tapped.
+    pub fn navigation(label: &str, description: Option<&str>, flow: Flow) -> Self {
+        Input::Navigation {label: label.to_string(), description: description.map(|d| d.to_string()), flow}
     }
 
     pub fn build(&self, ctx: &mut Context) -> Option<Vec<Box<dyn Drawable>>> {
         Some(match self {
-            Input::Text {label, tag, ..} => drawables![TextInput::new(ctx, None, (label, false), Some(&format!("Enter {}...", label.to_lowercase())), None, None, tag)],
-            Input::Enumerator {items, tag} => drawables![RadioSelector::new(ctx, 0, tag, items.iter().map(|item| item.get()).collect::<Vec<_>>())],
-            Input::Currency {instructions, tag, ..} => drawables![NumericalInput::currency(ctx, instructions, tag)],
-            Input::Date {instructions, tag, ..} => drawables![NumericalInput::date(ctx, instructions, tag)],
-            Input::Time {instructions, tag, ..} => drawables![NumericalInput::time(ctx, instructions, tag)],
-            Input::Avatar {content, flair, action} => drawables![Avatar::new(ctx, content.clone(), flair.clone(), flair.is_some(), AvatarSize::Xxl, action.as_ref().map(|a| a.get()))],
-            Input::Boolean {items} => drawables![CheckboxList::new(items.iter().map(|item| item.get(ctx)).collect::<Vec<_>>())]
+            Input::Text {label, placeholder, multiline, max_length, mask, suggestions, actions, tag, check} => {
+                let placeholder = placeholder.clone().unwrap_or_else(|| format!("Enter {}...", label.to_lowercase()));
+                let mut items = drawables![
+                    TextField::new(ctx, label, &placeholder, *multiline, *max_length, mask.clone(), tag),
+                    ValidationMessage::new(Some(tag), check.clone())
+                ];
+
+                if let Some(source) = suggestions {
+                    items.push(Box::new(Suggestions::new(ctx, source.clone(), tag)));
+                }
+
+                if let Some(actions) = actions.as_ref().filter(|a| !a.is_empty()) {
+                    let buttons = actions.iter().map(|a| Button::secondary(ctx, Some(&a.icon), &a.label, None, a.get(tag), None)).collect::<Vec<_>>();
+                    items.push(Box::new(QuickActions::new(buttons)));
+                }
+
+                items
+            },
+            Input::Enumerator {items, tag, default, check} => {
+                // Only an enumerator with a default starts with an item picked, so `validators::selected` can fail.
+                let current = ctx.state().get_named::<String>(tag).cloned();
+                let is_item = |title: &String| items.iter().any(|item| &item.title == title);
+                if !current.as_ref().is_some_and(is_item) {
+                    if let Some(item) = default.as_ref().and_then(|d| items.iter().find(|item| &item.title == d)) {
+                        Dependency::set_named(ctx.state(), tag, item.title.clone());
+                        if let Some(action) = item.action.as_ref() { (action.get())(ctx) }
+                    }
+                }
+
+                drawables![Choices::new(ctx, items.clone(), tag), ValidationMessage::new(Some(tag), check.clone())]
+            },
+            Input::Currency {instructions, currency, convert_to, tag, check} => {
+                let mut items = vec![ConvertedAmount::input(ctx, instructions, tag, *currency, *convert_to)];
+                if let Some(to) = convert_to { items.push(Box::new(ConvertedAmount::new(ctx, tag, *currency, *to))); }
+                items.push(Box::new(ValidationMessage::new(Some(tag), check.clone())));
+                items
+            },
+            Input::Date {instructions, tag, check} => drawables![NumericalInput::date(ctx, instructions, tag), ValidationMessage::new(Some(tag), check.clone())],
+            Input::Time {instructions, tag, check} => drawables![NumericalInput::time(ctx, instructions, tag), ValidationMessage::new(Some(tag), check.clone())],
+            Input::Avatar {content, flair, action, tag} => {
+                let content = tag.as_ref().and_then(|t| ctx.state().get_named::<AvatarContent>(t).cloned()).unwrap_or(content.clone());
+                drawables![Avatar::new(ctx, content, flair.clone(), flair.is_some(), AvatarSize::Xxl, action.as_ref().map(|a| a.get()))]
+            },
+            Input::Boolean {items, check} => drawables![
+                CheckboxList::new(items.iter().map(|item| item.get(ctx)).collect::<Vec<_>>()),
+                ValidationMessage::new(None, check.clone())
+            ],
+            Input::Secure {kind, label, tag, check} => drawables![
+                SecureInput::new(ctx, kind.clone(), label, tag),
+                ValidationMessage::new(Some(tag), check.clone())
+            ],
+            Input::Picker {label, items, multiple, tag, check} => drawables![
+                Picker::new(ctx, label, items.clone(), *multiple, tag),
+                ValidationMessage::new(None, check.clone())
+            ],
+            Input::Scan {instructions, tag, check} => drawables![Scanner::new(ctx, instructions, tag), ValidationMessage::new(Some(tag), check.clone())],
+            Input::Toggle {label, description, tag, default} => {
+                if ctx.state().get_named::<bool>(tag).is_none() { Dependency::set_named(ctx.state(), tag, *default); }
+                drawables![Switch::new(ctx, label, description.as_deref(), tag)]
+            },
+            Input::Segmented {label, options, tag, default} => {
+                if !ctx.state().get_named::<String>(tag).is_some_and(|v| options.contains(v)) { Dependency::set_named(ctx.state(), tag, default.clone()); }
+                drawables![Segmented::new(ctx, label, options.clone(), tag)]
+            },
+            Input::DateTime {instructions, tag, check} => drawables![
+                DateTimeInput::new(ctx, instructions, tag),
+                ValidationMessage::new(Some(&format!("{tag}Date")), check.clone())
+            ],
+            Input::DateRange {instructions, presets, tag, check} => drawables![
+                DateRangeInput::new(ctx, instructions, presets, tag),
+                ValidationMessage::new(Some(&format!("{tag}Start")), check.clone())
+            ],
+            Input::Navigation {label, description, flow} => drawables![PelicanListItem::new(ctx, None,
+                ListItemInfoLeft::new(label, description.as_deref(), None, None),
+                None, None, Some("forward"),
+                flow.clone().build()
+            )]
         })
     }
 
+    /// Resets the value stored under this input's tag.
+    pub fn clear(&self, state: &mut State) {
+        match self {
+            Input::Currency {tag, convert_to: Some(_), ..} => {
+                Dependency::set_named(state, tag, String::new());
+                Dependency::set_named(state, &format!("{tag}Amount"), None::<Money>);
+                Dependency::set_named(state, &format!("{tag}Swapped"), false);
+            },
+            Input::Text {tag, ..} |
+            Input::Currency {tag, ..} |
+            Input::Date {tag, ..} |
+            Input::Time {tag, ..} |
+            Input::Enumerator {tag, ..} |
+            Input::Scan {tag, ..} => Dependency::set_named(state, tag, String::new()),
+            Input::Avatar {content, tag: Some(tag), ..} => Dependency::set_named(state, tag, content.clone()),
+            Input::Avatar {tag: None, ..} => {},
+            Input::Boolean {items, ..} => items.iter().for_each(|item| Dependency::set_named(state, &item.tag, item.is_selected)),
+            Input::Secure {tag, ..} => {
+                Dependency::set_named(state, tag, Secret::default());
+                state.set_named(format!("{tag}Revealed"), false);
+            },
+            Input::Picker {tag, multiple: false, ..} => Dependency::set_named(state, tag, String::new()),
+            Input::Picker {tag, multiple: true, ..} => Dependency::set_named(state, tag, Vec::<String>::new()),
+            Input::Toggle {tag, default, ..} => Dependency::set_named(state, tag, *default),
+            Input::Segmented {tag, default, ..} => Dependency::set_named(state, tag, default.clone()),
+            Input::DateTime {tag, ..} => {
+                Dependency::set_named(state, &format!("{tag}Date"), String::new());
+                Dependency::set_named(state, &format!("{tag}Time"), String::new());
+                Dependency::set_named(state, tag, None::<NaiveDateTime>);
+            },
+            Input::DateRange {tag, ..} => {
+                Dependency::set_named(state, &format!("{tag}Start"), String::new());
+                Dependency::set_named(state, &format!("{tag}End"), String::new());
+                Dependency::set_named(state, tag, None::<DateRange>);
+            },
+            Input::Navigation {..} => {},
+        }
+    }
+
     pub fn check(&mut self) -> Option<Box<dyn ValidityFn>> {
         match self {
-            Input::Text {check, ..} => Some(check.clone()),
-            Input::Currency {check, ..} => Some(check.clone()),
-            _ => None
+            Input::Text {check, ..} |
+            Input::Currency {check, ..} |
+            Input::Date {check, ..} |
+            Input::Time {check, ..} |
+            Input::Enumerator {check, ..} |
+            Input::Boolean {check, ..} |
+            Input::Secure {check, ..} |
+            Input::Picker {check, ..} |
+            Input::Scan {check, ..} |
+            Input::DateTime {check, ..} |
+            Input::DateRange {check, ..} => Some(check.clone()),
+            Input::Avatar {..} |
+            Input::Toggle {..} |
+            Input::Segmented {..} |
+            Input::Navigation {..} => None
         }
     }
+
+    pub fn is_secure(&self) -> bool {
+        matches!(self, Input::Secure {..})
+    }
 }
 
 #[derive(Debug, Clone)]
 pub enum Display {
     Text {text: String, size: TextSize, style: TextStyle, align: Align},
     Icon {icon: String},
+    Image {source: ImageSource, fit: ImageFit, aspect_ratio: f32, width: f32},
     Review {label: String, data: String, instructions: String},
     Table {label: String, items: Vec<TableItem>},
-    Currency {amount: f32, instructions: String},
-    List {label: Option<String>, items: Vec<ListItem>, flow: Option<Flow>, instructions: Option<String>},
+    Currency {amount: Money, secondary: Option<Money>, convert_to: Option<Currency>},
+    List {label: Option<String>, items: Vec<ListItem>, flow: Option<Flow>, instructions: Option<String>, illustration: Option<String>},
     QRCode {data: String, instructions: String},
-    Avatar {content: AvatarContent}
+    Avatar {content: AvatarContent},
+    Chart {label: String, kind: ChartKind, series: Vec<(DateTime<Utc>, f64)>},
+    Banner {severity: Severity, text: String, dismiss: Option<String>, action: Option<(String, Action)>},
 }
 
 impl Display {
@@ -99,6 +374,16 @@ impl Display {
         Display::Icon {icon: icon.to_string()}
     }
 
+    /// An SVG bundled with the app's assets, such as `brand/illustrations/toucan.svg`.
+    pub fn illustration(path: &str) -> Self {
+        Display::Image {source: ImageSource::Asset(path.to_string()), fit: ImageFit::Fit, aspect_ratio: 1.0, width: 160.0}
+    }
+
+    /// A full width image framed at `aspect_ratio` (width over height).
+    pub fn image(source: ImageSource, fit: ImageFit, aspect_ratio: f32) -> Self {
+        Display::Image {source, fit, aspect_ratio, width: 320.0}
+    }
+
     pub fn review(label: &str, data: &str, instructions: &str) -> Self {
         Display::Review {label: label.to_string(), data: data.to_string(), instructions: instructions.to_string()}
     }
@@ -112,28 +397,75 @@ impl Display {
     }
 
     pub fn list(label: Option<&str>, items: Vec<ListItem>, flow: Option<Flow>, instructions: Option<&str>) -> Self {
-        Display::List{label: label.map(|i| i.to_string()), items, flow, instructions: instructions.map(|i| i.to_string())}
+        Display::List{label: label.map(|i| i.to_string()), items, flow, instructions: instructions.map(|i| i.to_string()), illustration: None}
+    }
+
+    /// Shows the illustration at `path` above an empty list's instructions. Has no effect on other displays.
+    pub fn with_illustration(mut self, path: &str) -> Self {
+        if let Display::List {illustration, ..} = &mut self { *illustration = Some(path.to_string()); }
+        self
+    }
+
+    /// A large amount, with the same value in a `secondary` currency underneath it, such as USD over BTC.
+    pub fn currency(amount: Money, secondary: Option<Money>) -> Self {
+        Display::Currency {amount, secondary, convert_to: None}
     }
 
-    pub fn currency(amount: f32, instructions: &str) -> Self {
-        Display::Currency {amount, instructions: instructions.to_string()}
+    /// A large amount, with its value in `to` underneath it converted by the registered [`RateProvider`](crate::RateProvider).
+    pub fn converted(amount: Money, to: Currency) -> Self {
+        Display::Currency {amount, secondary: None, convert_to: Some(to)}
     }
 
     pub fn avatar(content: AvatarContent) -> Self {
         Display::Avatar {content}
     }
 
+    /// A line chart of `series` in the theme's brand color, with buttons to show the last day, week, month or year.
+    pub fn line_chart(label: &str, series: Vec<(DateTime<Utc>, f64)>) -> Self {
+        Display::Chart {label: label.to_string(), kind: ChartKind::Line, series}
+    }
+
+    /// A bar chart of `series`, averaging the values that fall under each bar.
+    pub fn bar_chart(label: &str, series: Vec<(DateTime<Utc>, f64)>) -> Self {
+        Display::Chart {label: label.to_string(), kind: ChartKind::Bar, series}
+    }
+
+    /// A short message called out with the icon and color of its `severity`, such as a warning before an irreversible step.
+    pub fn banner(severity: Severity, text: &str) -> Self {
+        Display::Banner {severity, text: text.to_string(), dismiss: None, action: None}
+    }
+
+    /// Lets a banner be dismissed. It stays hidden for as long as `tag` holds `true`. Has no effect on other displays.
+    pub fn with_dismiss(mut self, tag: &str) -> Self {
+        if let Display::Banner {dismiss, ..} = &mut self { *dismiss = Some(tag.to_string()); }
+        self
+    }
+
+    /// Adds a button such as "Learn more" to a banner. Has no effect on other displays.
+    pub fn with_action(mut self, label: &str, on_click: Action) -> Self {
+        if let Display::Banner {action, ..} = &mut self { *action = Some((label.to_string(), on_click)); }
+        self
+    }
+
     pub fn build(&mut self, ctx: &mut Context) -> Option<Vec<Box<dyn Drawable>>> {
         Some(match self {
             Display::Icon {icon} => {
                 let color = ctx.get::<PelicanUI>().get().0.theme().colors.text.heading;
                 drawables![Icon::new(ctx, icon, Some(color), 128.0)]
             }
+            Display::Image {source, fit, aspect_ratio, width} => drawables![source.load(ctx, *fit, (*width, *width / aspect_ratio.max(0.01)))?],
             Display::Text {text, size, style, align} => drawables![ExpandableText::new(ctx, text, *size, *style, *align, None)],
             Display::Review {label, data, instructions} => drawables![DataItem::text(ctx, label, data, instructions, None)],
             Display::Table {label, items} => drawables![DataItem::table(ctx, label, items.iter().map(|TableItem{title, data}| (title.clone(), data.clone())).collect(), None)],
-            Display::Currency {amount, instructions} => drawables![NumericalInput::display(ctx, *amount, instructions)],
-            Display::List {items, instructions, ..} if items.is_empty() => drawables![ExpandableText::new(ctx, instructions.as_ref()?, TextSize::Md, TextStyle::Secondary, Align::Center, None)],
+            Display::Currency {amount, secondary, convert_to} => drawables![CurrencyDisplay::new(ctx, *amount, *secondary, *convert_to)],
+            Display::List {items, instructions, illustration, ..} if items.is_empty() => {
+                let mut empty = illustration.as_ref().and_then(|path| Display::illustration(path).build(ctx)).unwrap_or_default();
+                if let Some(text) = instructions {
+                    empty.push(Box::new(ExpandableText::new(ctx, text, TextSize::Md, TextStyle::Secondary, Align::Center, None)));
+                }
+                if empty.is_empty() { return None; }
+                empty
+            },
             Display::List {label, items, flow, ..} => {
                 let mut list_items = Vec::new();
 
@@ -150,35 +482,46 @@ impl Display {
             }
             Display::QRCode {data, instructions} => drawables![QRCode::new(ctx, data), ExpandableText::new(ctx, instructions, TextSize::Md, TextStyle::Secondary, Align::Center, None)],
             Display::Avatar {content} => drawables![Avatar::new(ctx, content.clone(), None, false, AvatarSize::Xxl, None)],
+            Display::Chart {label, kind, series} => drawables![Chart::new(ctx, label, *kind, series.clone())],
+            Display::Banner {severity, text, dismiss, action} => drawables![Banner::new(ctx, *severity, text, dismiss.clone(), action.clone())],
         })
     }
 }
 
 #[derive(Debug, Clone)]
-pub struct ListItem {avatar: Option<AvatarContent>, title: String, subtitle: String, secondary: Option<String>}
+pub struct ListItem {avatar: Option<AvatarContent>, title: String, subtitle: String, secondary: Option<String>, tag: String}
 
 impl ListItem {
-    pub fn plain(title: &str, subtitle: &str, secondary: Option<&str>, _tag: &str) -> Self {
+    pub fn plain(title: &str, subtitle: &str, secondary: Option<&str>, tag: &str) -> Self {
         ListItem {
             avatar: None,
             title: title.to_string(),
             subtitle: subtitle.to_string(),
             secondary: secondary.map(|s| s.to_string()),
+            tag: tag.to_string(),
         }
     }
 
-    pub fn avatar(avatar: AvatarContent, title: &str, subtitle: &str, secondary: Option<&str>, _tag: &str) -> Self {
+    pub fn avatar(avatar: AvatarContent, title: &str, subtitle: &str, secondary: Option<&str>, tag: &str) -> Self {
         ListItem {
             avatar: Some(avatar),
             title: title.to_string(),
             subtitle: subtitle.to_string(),
             secondary: secondary.map(|s| s.to_string()),
+            tag: tag.to_string(),
         }
     }
 
     pub(crate) fn build(&self, ctx: &mut Context, mut flow: Option<&mut Flow>) -> PelicanListItem {
-        let ListItem {avatar, title, subtitle, secondary} = self;
-        let closure = flow.as_mut().map(|f| f.build());
+        let ListItem {avatar, title, subtitle, secondary, tag} = self;
+        let closure = flow.as_mut().map(|f| {
+            let mut on_click = f.build();
+            let selection = Selection(tag.to_string());
+            Box::new(move |ctx: &mut Context| {
+                ctx.state().set(selection.clone());
+                (on_click)(ctx)
+            }) as Callback
+        });
 
         PelicanListItem::new(ctx, avatar.clone(), 
             ListItemInfoLeft::new(title, Some(subtitle), None, None), 
@@ -187,24 +530,71 @@ impl ListItem {
             closure.unwrap_or(Box::new(|_ctx: &mut Context| {}))
         )
     }
+
+    /// Whether the title or subtitle contains `query`, ignoring case.
+    pub(crate) fn matches(&self, query: &str) -> bool {
+        let query = query.trim().to_lowercase();
+        self.title.to_lowercase().contains(&query) || self.subtitle.to_lowercase().contains(&query)
+    }
+
+    pub(crate) fn tag(&self) -> &str {
+        &self.tag
+    }
+
+    /// Builds this item as an entry of a [`Picker`], writing its tag to `tag` when tapped.
+    pub(crate) fn build_pickable(&self, ctx: &mut Context, tag: &str, multiple: bool, is_picked: bool) -> PelicanListItem {
+        let (tag, value) = (tag.to_string(), self.tag.clone());
+        let on_click = Box::new(move |ctx: &mut Context| match multiple {
+            true => {
+                let picked = ctx.state().get_named::<Vec<String>>(&tag).cloned().unwrap_or_default();
+                let picked = match picked.contains(&value) {
+                    true => picked.into_iter().filter(|v| v != &value).collect::<Vec<_>>(),
+                    false => picked.into_iter().chain([value.clone()]).collect(),
+                };
+                Dependency::set_named(ctx.state(), &tag, picked);
+            },
+            false => Dependency::set_named(ctx.state(), &tag, value.clone()),
+        });
+
+        PelicanListItem::new(ctx, self.avatar.clone(),
+            ListItemInfoLeft::new(&self.title, Some(&self.subtitle), None, None),
+            self.secondary.as_ref().map(|s| TitleSubtitle::new(s, Some("Details"))),
+            None, is_picked.then_some("checkmark"),
+            on_click
+        )
+    }
+}
+
+/// The tag of the most recently tapped [`ListItem`], set right before its flow is started.
+#[derive(Debug, Clone, Default)]
+pub struct Selection(String);
+
+impl Selection {
+    pub fn tag(&self) -> &str {
+        &self.0
+    }
 }
 
 #[derive(Debug, Clone)]
 pub enum Action {
     Share {data: String},
-    SelectImage,
+    SelectImage {tag: String},
     Custom {action: Box<dyn FnMutClone>},
     None,
     Navigate {flow: Flow},
 }
 
 impl Action {
+    /// Shares `data` with the registered [`ShareProvider`](crate::providers::ShareProvider). chk registers one that copies
+    /// to the clipboard on Linux; other hosts register their own, or an error toast is shown instead.
     pub fn share(data: &str) -> Self {
         Action::Share {data: data.to_string()}
     }
 
-    pub fn select_image() -> Self {
-        Action::SelectImage
+    /// Picks an image with the registered [`ImagePicker`](crate::providers::ImagePicker), crops it to a square
+    /// and stores it under `tag` as an [`AvatarContent`].
+    pub fn select_image(tag: &str) -> Self {
+        Action::SelectImage {tag: tag.to_string()}
     }
 
     pub fn custom(action: impl FnMutClone + 'static) -> Self {
@@ -219,11 +609,27 @@ impl Action {
         match self {
             Action::Share {data} => {
                 let share_data = data.clone();
-                Box::new(move |_ctx: &mut Context| println!("Sharing data {:?}", share_data.clone()))
+                Box::new(move |ctx: &mut Context| match Providers::share(ctx) {
+                    Some(provider) => provider.borrow_mut().share(ctx, &share_data),
+                    None => toast(ctx, Severity::Error, "Sharing isn't available on this device"),
+                })
             }
 
-            Action::SelectImage => {
-                Box::new(move |_ctx: &mut Context| println!("Selecting image"))
+            Action::SelectImage {tag} => {
+                let tag = tag.clone();
+                Box::new(move |ctx: &mut Context| {
+                    let Some(picker) = Providers::image_picker(ctx) else { return toast(ctx, Severity::Error, "Choosing an image isn't available on this device") };
+                    let Some(bytes) = picker.borrow_mut().pick(ctx) else { return };
+
+                    match image::load_from_memory(&bytes).map(square) {
+                        Ok(Some(picked)) => {
+                            Dependency::set_named(ctx.state(), &tag, AvatarContent::Image(picked));
+                            refresh(ctx);
+                        }
+                        Ok(None) => toast(ctx, Severity::Error, "The selected image is empty"),
+                        Err(_) => toast(ctx, Severity::Error, "The selected image couldn't be opened"),
+                    }
+                })
             }
 
             Action::Custom {action} => {
@@ -238,6 +644,216 @@ impl Action {
     }
 }
 
+/// Crops the largest centered square out of `picked` and scales it to the avatar resolution, or returns `None` if it has no pixels.
+fn square(picked: image::DynamicImage) -> Option<image::RgbaImage> {
+    if picked.width() == 0 || picked.height() == 0 { return None; }
+    Some(crop(&picked, 1.0).resize_exact(512, 512, image::imageops::FilterType::Lanczos3).to_rgba8())
+}
+
+/// The largest centered part of `picked` that is `aspect_ratio` times as wide as it is high.
+fn crop(picked: &image::DynamicImage, aspect_ratio: f32) -> image::DynamicImage {
+    let (width, height) = (picked.width() as f32, picked.height() as f32);
+    let (w, h) = if width / height > aspect_ratio {(height * aspect_ratio, height)} else {(width, width / aspect_ratio)};
+    let (w, h) = ((w as u32).clamp(1, picked.width().max(1)), (h as u32).clamp(1, picked.height().max(1)));
+    picked.crop_imm(picked.width().saturating_sub(w) / 2, picked.height().saturating_sub(h) / 2, w, h)
+}
+
+/// How urgent a [`Display::banner`] is, which picks its icon and color.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Severity {
+    Info,
+    Warning,
+    Error,
+    Success,
+}
+
+impl Severity {
+    pub fn icon(&self) -> &'static str {
+        match self {
+            Severity::Info => "info",
+            Severity::Warning => "warning",
+            Severity::Error => "error",
+            Severity::Success => "checkmark",
+        }
+    }
+}
+
+/// Where a [`Display::image`] is loaded from.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ImageSource {
+    /// An SVG or PNG bundled with the app's assets, such as `brand/illustrations/dodo.svg`.
+    Asset(String),
+    /// An image on disk, such as one the user picked.
+    File(PathBuf),
+}
+
+/// How an image fills the frame it is shown in.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum ImageFit {
+    /// Shows the whole image, shrinking the frame to its proportions.
+    #[default]
+    Fit,
+    /// Covers the whole frame, cropping whatever of the image falls outside it. SVGs can't be cropped, so they are always fitted.
+    Fill,
+}
+
+impl ImageSource {
+    fn is_svg(&self) -> bool {
+        let path = match self {
+            ImageSource::Asset(path) => Path::new(path),
+            ImageSource::File(path) => path.as_path(),
+        };
+        path.extension().is_some_and(|e| e.eq_ignore_ascii_case("svg"))
+    }
+
+    fn load(&self, ctx: &mut Context, fit: ImageFit, frame: (f32, f32)) -> Option<Image> {
+        let bytes = match self {
+            ImageSource::Asset(path) => ctx.assets.load_file(path)?,
+            ImageSource::File(path) => std::fs::read(path).map_err(|e| println!("Could not read {path:?}: {e}")).ok()?,
+        };
+
+        let (image, size) = match self.is_svg() {
+            true => (ctx.assets.add_svg(&bytes, 8.0), fitted(svg_size(&bytes).unwrap_or(frame), frame)),
+            false => {
+                let decoded = image::load_from_memory(&bytes).map_err(|e| println!("Could not decode {self:?}: {e}")).ok()?;
+                match fit {
+                    ImageFit::Fit => (ctx.assets.add_image(decoded.to_rgba8()), fitted((decoded.width() as f32, decoded.height() as f32), frame)),
+                    ImageFit::Fill => (ctx.assets.add_image(crop(&decoded, frame.0 / frame.1).to_rgba8()), frame),
+                }
+            }
+        };
+
+        Some(Image {shape: ShapeType::Rectangle(0.0, size, 0.0), image, color: None})
+    }
+}
+
+/// The largest size with the proportions of `size` that fits inside `frame`.
+fn fitted((width, height): (f32, f32), frame: (f32, f32)) -> (f32, f32) {
+    let scale = (frame.0 / width.max(1.0)).min(frame.1 / height.max(1.0));
+    (width * scale, height * scale)
+}
+
+/// The width and height an SVG was drawn at, read from its `viewBox` or its `width` and `height` attributes.
+fn svg_size(bytes: &[u8]) -> Option<(f32, f32)> {
+    let svg = String::from_utf8_lossy(bytes);
+    let view_box = Regex::new(r#"viewBox\s*=\s*"\s*[-\d.]+[\s,]+[-\d.]+[\s,]+([\d.]+)[\s,]+([\d.]+)"#).ok()?;
+    let attributes = Regex::new(r#"<svg[^>]*?\swidth\s*=\s*"([\d.]+)[^"]*"[^>]*?\sheight\s*=\s*"([\d.]+)"#).ok()?;
+    let captures = view_box.captures(&svg).or_else(|| attributes.captures(&svg))?;
+    Some((captures[1].parse().ok()?, captures[2].parse().ok()?))
+}
+
+/// Formats the digits typed into an [`Input::masked`] field, where each `#` of the pattern takes one digit.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Mask {
+    Phone,
+    Card,
+    Date,
+    Custom(String),
+}
+
+impl Mask {
+    pub fn pattern(&self) -> &str {
+        match self {
+            Mask::Phone => "(###) ###-####",
+            Mask::Card => "#### #### #### ####",
+            Mask::Date => "##/##/####",
+            Mask::Custom(pattern) => pattern,
+        }
+    }
+
+    /// Lays the digits of `value` into the pattern, stopping at the first `#` there are no digits left for.
+    pub fn apply(&self, value: &str) -> String {
+        let mut digits = value.chars().filter(|c| c.is_ascii_digit()).peekable();
+        let mut formatted = String::new();
+        for c in self.pattern().chars() {
+            match c {
+                '#' => match digits.next() {
+                    Some(digit) => formatted.push(digit),
+                    None => break,
+                },
+                _ if digits.peek().is_some() => formatted.push(c),
+                _ => break,
+            }
+        }
+        formatted
+    }
+
+    pub fn is_complete(&self, value: &str) -> bool {
+        self.apply(value).chars().count() == self.pattern().chars().count()
+    }
+}
+
+/// A chip under an [`Input::Text`] that fills the field with a value from somewhere else.
+#[derive(Debug, Clone)]
+pub struct QuickAction {label: String, icon: String, kind: QuickActionKind}
+
+#[derive(Debug, Clone)]
+enum QuickActionKind {
+    Paste,
+    Custom(Box<dyn FillFn>),
+    Flow(Flow, String),
+}
+
+impl QuickAction {
+    /// Fills the field with the clipboard's contents.
+    pub fn paste() -> Self {
+        QuickAction {label: "Paste".to_string(), icon: "paste".to_string(), kind: QuickActionKind::Paste}
+    }
+
+    /// Fills the field with whatever `fill` returns, leaving it untouched on `None`.
+    pub fn custom(label: &str, icon: &str, fill: impl FnMut(&mut Context) -> Option<String> + Clone + 'static) -> Self {
+        QuickAction {label: label.to_string(), icon: icon.to_string(), kind: QuickActionKind::Custom(Box::new(fill))}
+    }
+
+    /// Runs `flow` and, as soon as a value is stored under `result_tag`, copies it into the field and closes the flow.
+    pub fn flow(label: &str, icon: &str, flow: Flow, result_tag: &str) -> Self {
+        QuickAction {label: label.to_string(), icon: icon.to_string(), kind: QuickActionKind::Flow(flow, result_tag.to_string())}
+    }
+
+    fn get(&self, tag: &str) -> Callback {
+        let tag = tag.to_string();
+        match &self.kind {
+            QuickActionKind::Paste => Box::new(move |ctx: &mut Context| {
+                let Some(clipboard) = Providers::clipboard(ctx) else { return };
+                let pasted = clipboard.borrow_mut().paste(ctx);
+                if let Some(value) = pasted { fill(ctx, &tag, value) }
+            }),
+            QuickActionKind::Custom(action) => {
+                let mut action = action.clone();
+                Box::new(move |ctx: &mut Context| if let Some(value) = (action)(ctx) { fill(ctx, &tag, value) })
+            }
+            QuickActionKind::Flow(flow, result_tag) => {
+                let mut start = flow.clone().build();
+                let result_tag = result_tag.clone();
+                Box::new(move |ctx: &mut Context| {
+                    Dependency::set_named(ctx.state(), &result_tag, String::new());
+                    ctx.state().set(Returning(Some((result_tag.clone(), tag.clone()))));
+                    (start)(ctx)
+                })
+            }
+        }
+    }
+}
+
+fn fill(ctx: &mut Context, tag: &str, value: String) {
+    Dependency::set_named(ctx.state(), tag, value);
+    refresh(ctx);
+}
+
+/// A [`QuickAction::flow`] waiting for its flow to produce a value, as (result tag, input tag).
+#[derive(Debug, Clone, Default)]
+pub(crate) struct Returning(Option<(String, String)>);
+
+impl Returning {
+    pub fn poll(ctx: &mut Context) {
+        let Some((result_tag, tag)) = ctx.state().get_or_default::<Returning>().0.clone() else { return };
+        let Some(value) = ctx.state().get_named::<String>(&result_tag).filter(|v| !v.is_empty()).cloned() else { return };
+        ctx.state().set(Returning(None));
+        fill(ctx, &tag, value);
+        ActiveFlows::complete_top(ctx);
+    }
+}
+
 #[derive(Debug, Clone)]
 pub struct TableItem {title: String, data: String}
 
@@ -248,26 +864,40 @@ impl TableItem {
 }
 
 #[derive(Debug, Clone)]
-pub struct EnumItem {title: String, data: String}
+pub struct EnumItem {title: String, data: String, action: Option<Action>}
 impl EnumItem {
     pub fn new(title: &str, data: &str) -> Self {
-        EnumItem {title: title.to_string(), data: data.to_string()}
+        EnumItem {title: title.to_string(), data: data.to_string(), action: None}
     }
 
-    fn get(&self) -> (&str, &str, Callback) {
-        (&self.title as &str, &self.data as &str, Box::new(|_ctx: &mut Context| {}) as Box<dyn FnMut(&mut Context)>)
+    /// Runs `action` whenever this item becomes the selected one, including when it is selected by default.
+    pub fn on_select(mut self, action: Action) -> Self {
+        self.action = Some(action);
+        self
+    }
+
+    pub(crate) fn get(&self, tag: &str) -> (&str, &str, Callback) {
+        let (tag, title) = (tag.to_string(), self.title.clone());
+        let mut action = self.action.as_ref().map(|a| a.get());
+        let on_select = Box::new(move |ctx: &mut Context| {
+            Dependency::set_named(ctx.state(), &tag, title.clone());
+            if let Some(action) = action.as_mut() { (action)(ctx) }
+        });
+        (&self.title as &str, &self.data as &str, on_select as Box<dyn FnMut(&mut Context)>)
     }
 }
 
 #[derive(Debug, Clone)]
-pub struct ChecklistItem {title: String, subtitle: Option<String>, is_selected: bool}
+pub struct ChecklistItem {title: String, subtitle: Option<String>, tag: String, is_selected: bool}
 impl ChecklistItem {
-    pub fn new(title: &str, subtitle: Option<&str>, is_selected: bool) -> Self {
-        ChecklistItem {title: title.to_string(), subtitle: subtitle.map(|s| s.to_string()), is_selected}
+    pub fn new(title: &str, subtitle: Option<&str>, tag: &str, is_selected: bool) -> Self {
+        ChecklistItem {title: title.to_string(), subtitle: subtitle.map(|s| s.to_string()), tag: tag.to_string(), is_selected}
     }
 
     fn get(&self, ctx: &mut Context) -> Checkbox {
-        Checkbox::new(ctx, &self.title, self.subtitle.clone(), self.is_selected, &self.title)
+        let is_selected = *ctx.state().get_named::<bool>(&self.tag).unwrap_or(&self.is_selected);
+        Dependency::set_named(ctx.state(), &self.tag, is_selected);
+        Checkbox::new(ctx, &self.title, self.subtitle.clone(), is_selected, &self.tag)
     }
 }
 
@@ -325,11 +955,11 @@ impl std::fmt::Debug for dyn FnMutClone {
     }
 }
 
-pub trait ValidityFn: FnMut(&mut Context) -> bool + 'static {
+pub trait ValidityFn: FnMut(&mut Context) -> Validation + 'static {
     fn clone_box(&self) -> Box<dyn ValidityFn>;
 }
 
-impl<F> ValidityFn for F where F: FnMut(&mut Context) -> bool + Clone + 'static {
+impl<F> ValidityFn for F where F: FnMut(&mut Context) -> Validation + Clone + 'static {
     fn clone_box(&self) -> Box<dyn ValidityFn> {
         Box::new(self.clone())
     }
@@ -345,4 +975,90 @@ impl std::fmt::Debug for dyn ValidityFn {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         write!(f, "Valitidy check...")
     }
-}
\ No newline at end of file
+}
+
+pub trait FillFn: FnMut(&mut Context) -> Option<String> + 'static {
+    fn clone_box(&self) -> Box<dyn FillFn>;
+}
+
+impl<F> FillFn for F where F: FnMut(&mut Context) -> Option<String> + Clone + 'static {
+    fn clone_box(&self) -> Box<dyn FillFn> {
+        Box::new(self.clone())
+    }
+}
+
+impl Clone for Box<dyn FillFn> {
+    fn clone(&self) -> Self {
+        self.as_ref().clone_box()
+    }
+}
+
+impl std::fmt::Debug for dyn FillFn {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Fill...")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::providers::{FileImagePicker, ImagePicker};
+
+    #[test]
+    fn mask_lays_digits_into_pattern() {
+        assert_eq!(Mask::Phone.apply("5551234567"), "(555) 123-4567");
+        assert_eq!(Mask::Card.apply("4111-1111 1111 1111"), "4111 1111 1111 1111");
+        assert_eq!(Mask::Date.apply("123120249"), "12/31/2024");
+        assert_eq!(Mask::Custom("##-##".to_string()).apply("1a2b3"), "12-3");
+    }
+
+    #[test]
+    fn mask_stops_where_digits_run_out() {
+        assert_eq!(Mask::Phone.apply(""), "");
+        assert_eq!(Mask::Phone.apply("555"), "(555");
+        assert_eq!(Mask::Phone.apply("5551"), "(555) 1");
+        assert!(!Mask::Date.is_complete("1231"));
+        assert!(Mask::Date.is_complete("12312024"));
+    }
+
+    /// A 300×200 image, red in its left 50 columns, blue in its right 50 and green in between.
+    fn striped() -> image::DynamicImage {
+        image::DynamicImage::ImageRgba8(image::RgbaImage::from_fn(300, 200, |x, _| match x {
+            0..=49 => image::Rgba([255, 0, 0, 255]),
+            250.. => image::Rgba([0, 0, 255, 255]),
+            _ => image::Rgba([0, 255, 0, 255]),
+        }))
+    }
+
+    #[test]
+    fn crop_keeps_the_center() {
+        let cropped = crop(&striped(), 1.0).to_rgba8();
+        assert_eq!(cropped.dimensions(), (200, 200));
+        assert_eq!(cropped.get_pixel(0, 0).0, [0, 255, 0, 255]);
+        assert_eq!(cropped.get_pixel(199, 199).0, [0, 255, 0, 255]);
+
+        assert_eq!(crop(&striped(), 3.0).dimensions(), (300, 100));
+    }
+
+    #[test]
+    fn square_picks_a_centered_square() {
+        let path = std::env::temp_dir().join("chk-square-test.png");
+        striped().save(&path).unwrap();
+        let bytes = FileImagePicker::new(&path).pick(&mut Context::default()).unwrap();
+        let _ = std::fs::remove_file(&path);
+
+        let squared = square(image::load_from_memory(&bytes).unwrap()).unwrap();
+        assert_eq!(squared.dimensions(), (512, 512));
+        for (x, y) in [(8, 8), (256, 256), (503, 503)] {
+            let [r, g, b, _] = squared.get_pixel(x, y).0;
+            assert!(g > 200 && r < 50 && b < 50, "({x}, {y}) is {:?}", (r, g, b));
+        }
+    }
+
+    #[test]
+    fn empty_images_do_not_underflow() {
+        let empty = image::DynamicImage::new_rgba8(0, 10);
+        assert_eq!(crop(&empty, 1.0).width(), 0);
+        assert!(square(empty).is_none());
+    }
+}
@@ -0,0 +1,120 @@
+use pelican_ui::Context;
+use pelican_ui::components::interface::navigation::NavigationEvent;
+
+use std::sync::mpsc::{channel, Receiver, Sender, TryRecvError};
+use std::rc::Rc;
+
+use crate::{Action, NavFn};
+use crate::flow::ActiveFlows;
+use crate::pages::{PageType, BuildablePage, AppPage};
+
+/// The eventual outcome of a deferred `on_submit`. `Err` carries the message shown on the failure page.
+#[derive(Debug, Clone)]
+pub struct Submission(Rc<Receiver<Result<(), String>>>);
+
+impl Submission {
+    /// Runs `work` on a background thread.
+    pub fn spawn(work: impl FnOnce() -> Result<(), String> + Send + 'static) -> Self {
+        let (sender, submission) = Submission::channel();
+        std::thread::spawn(move || { let _ = sender.send(work()); });
+        submission
+    }
+
+    /// A submission that finishes whenever a result is sent, e.g. from a network callback.
+    pub fn channel() -> (Sender<Result<(), String>>, Self) {
+        let (sender, receiver) = channel();
+        (sender, Submission(Rc::new(receiver)))
+    }
+
+    pub fn ready(result: Result<(), String>) -> Self {
+        let (sender, submission) = Submission::channel();
+        let _ = sender.send(result);
+        submission
+    }
+
+    fn poll(&self) -> Option<Result<(), String>> {
+        match self.0.try_recv() {
+            Ok(result) => Some(result),
+            Err(TryRecvError::Empty) => None,
+            Err(TryRecvError::Disconnected) => Some(Err("The request stopped before it finished.".to_string())),
+        }
+    }
+}
+
+/// The submission a flow is waiting on, with what to do once it finishes.
+#[derive(Debug, Clone, Default)]
+pub(crate) struct Pending(Option<(Submission, Box<dyn SubmitFn>, NavWrapper)>);
+
+#[derive(Clone)]
+struct NavWrapper(NavFn);
+
+impl std::fmt::Debug for NavWrapper {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "NavFn")
+    }
+}
+
+impl Pending {
+    /// Starts the submission and shows the loading page until it finishes, then moves on to `success`.
+    /// Does nothing while another submission is still running.
+    pub fn submit(ctx: &mut Context, submit: Box<dyn SubmitFn>, success: NavFn) {
+        Self::start(ctx, submit, success, false);
+    }
+
+    /// Starts the submission, showing the loading page in place of the current page when `replace` is set.
+    fn start(ctx: &mut Context, mut submit: Box<dyn SubmitFn>, success: NavFn, replace: bool) {
+        if ctx.state().get_or_default::<Pending>().0.is_some() { return; }
+
+        let submission = (submit)(ctx);
+        ctx.state().set(Pending(Some((submission, submit, NavWrapper(success)))));
+        let page = PageType::loading("Submitting", "This may take a moment.").build(ctx);
+        Self::show(ctx, page, replace);
+    }
+
+    fn show(ctx: &mut Context, page: AppPage, replace: bool) {
+        if replace { ActiveFlows::pop(ctx); }
+        ActiveFlows::pushed(ctx, Vec::new(), false);
+        ctx.trigger_event(NavigationEvent::Push(Some(Box::new(page))));
+    }
+
+    /// Moves past the loading page once the submission finished, replacing it so it can never be gone back to.
+    pub fn poll(ctx: &mut Context) {
+        let Some((submission, submit, success)) = ctx.state().get_or_default::<Pending>().0.clone() else { return };
+        let Some(result) = submission.poll() else { return };
+        ctx.state().set(Pending(None));
+
+        match result {
+            Ok(()) => {
+                ActiveFlows::pop(ctx);
+                (success.0.borrow_mut())(ctx)
+            },
+            Err(message) => {
+                let retry = Action::custom(move |ctx: &mut Context| Pending::start(ctx, submit.clone(), success.0.clone(), true));
+                let page = PageType::failure("Something went wrong", &message, retry).build(ctx);
+                Self::show(ctx, page, true);
+            }
+        }
+    }
+}
+
+pub trait SubmitFn: FnMut(&mut Context) -> Submission + 'static {
+    fn clone_box(&self) -> Box<dyn SubmitFn>;
+}
+
+impl<F> SubmitFn for F where F: FnMut(&mut Context) -> Submission + Clone + 'static {
+    fn clone_box(&self) -> Box<dyn SubmitFn> {
+        Box::new(self.clone())
+    }
+}
+
+impl Clone for Box<dyn SubmitFn> {
+    fn clone(&self) -> Self {
+        self.as_ref().clone_box()
+    }
+}
+
+impl std::fmt::Debug for dyn SubmitFn {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Submit...")
+    }
+}
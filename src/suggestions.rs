@@ -0,0 +1,106 @@
+//! Autocomplete for [`Input::Text`](crate::Input) fields.
+//!
+//! A [`SuggestionSource`] reads candidates from [`State`] and the ones fuzzy-matching what has been typed
+//! are listed under the field, best match first.
+
+use pelican_ui::State;
+
+use std::collections::HashSet;
+
+/// Supplies the candidates suggested under a text input, such as recent recipients or existing project names.
+pub trait SuggestionSource: 'static {
+    fn candidates(&mut self, state: &mut State) -> Vec<String>;
+    fn clone_box(&self) -> Box<dyn SuggestionSource>;
+}
+
+impl<F> SuggestionSource for F where F: FnMut(&mut State) -> Vec<String> + Clone + 'static {
+    fn candidates(&mut self, state: &mut State) -> Vec<String> {
+        (self)(state)
+    }
+
+    fn clone_box(&self) -> Box<dyn SuggestionSource> {
+        Box::new(self.clone())
+    }
+}
+
+impl Clone for Box<dyn SuggestionSource> {
+    fn clone(&self) -> Self {
+        self.as_ref().clone_box()
+    }
+}
+
+impl std::fmt::Debug for dyn SuggestionSource {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Suggestions...")
+    }
+}
+
+/// Scores how well `candidate` matches `query`, or `None` if the characters of `query` don't all appear in it in order.
+///
+/// Matching ignores case. Matches at the start of the candidate or of a word, and runs of consecutive
+/// characters, score higher.
+pub fn fuzzy_score(query: &str, candidate: &str) -> Option<i32> {
+    let candidate = candidate.to_lowercase().chars().collect::<Vec<_>>();
+    let mut score = 0;
+    let mut next = 0;
+    let mut previous: Option<usize> = None;
+
+    for q in query.to_lowercase().chars().filter(|c| !c.is_whitespace()) {
+        let found = next + candidate[next..].iter().position(|c| *c == q)?;
+        score += 1;
+        if found == 0 || !candidate[found - 1].is_alphanumeric() { score += 8; }
+        if previous.is_some_and(|p| p + 1 == found) { score += 5; }
+        score -= (found - next).min(3) as i32;
+        previous = Some(found);
+        next = found + 1;
+    }
+
+    Some(score)
+}
+
+/// The best `limit` candidates for `query`, leaving out exact matches since there is nothing left to complete.
+pub fn rank(query: &str, candidates: Vec<String>, limit: usize) -> Vec<String> {
+    let query = query.trim();
+    if query.is_empty() { return Vec::new(); }
+
+    let mut seen = HashSet::new();
+    let mut scored = candidates.into_iter()
+        .filter(|c| !c.eq_ignore_ascii_case(query) && seen.insert(c.clone()))
+        .filter_map(|c| fuzzy_score(query, &c).map(|score| (score, c)))
+        .collect::<Vec<_>>();
+    scored.sort_by(|(a, x), (b, y)| b.cmp(a).then(x.len().cmp(&y.len())));
+    scored.into_iter().take(limit).map(|(_, c)| c).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn names(names: &[&str]) -> Vec<String> {
+        names.iter().map(|n| n.to_string()).collect()
+    }
+
+    #[test]
+    fn prefix_beats_scattered() {
+        assert!(fuzzy_score("ali", "Alice") > fuzzy_score("ali", "xaxlxi"));
+        assert_eq!(rank("ali", names(&["xaxlxi", "Alice"]), 5), names(&["Alice", "xaxlxi"]));
+    }
+
+    #[test]
+    fn out_of_order_does_not_match() {
+        assert_eq!(fuzzy_score("ba", "abc"), None);
+        assert_eq!(rank("ba", names(&["abc"]), 5), Vec::<String>::new());
+    }
+
+    #[test]
+    fn rank_dedupes_and_limits() {
+        assert_eq!(rank("al", names(&["Alice", "Alice", "Alan"]), 5), names(&["Alan", "Alice"]));
+        assert_eq!(rank("al", names(&["Alice", "Alice", "Alan"]), 1), names(&["Alan"]));
+    }
+
+    #[test]
+    fn rank_skips_exact_matches() {
+        assert_eq!(rank("alice", names(&["Alice", "Alice Smith"]), 5), names(&["Alice Smith"]));
+        assert_eq!(rank("  ", names(&["Alice"]), 5), Vec::<String>::new());
+    }
+}
@@ -0,0 +1,225 @@
+//! Built-in validity checks for [`Input`](crate::Input) fields.
+//!
+//! Each validator reads the value stored under an input's tag and returns a [`Validation`],
+//! whose error message is shown underneath the input while it blocks the page's bumper.
+
+use pelican_ui::{Context, State};
+use chrono::{NaiveDate, NaiveDateTime, NaiveTime};
+use regex::Regex;
+
+use crate::{ValidityFn, Mask, DateRange, Money, Currency, Locale};
+use crate::secret::Secret;
+
+use std::rc::Rc;
+
+/// The result of a validity check. `Err` carries the user-facing message.
+pub type Validation = Result<(), String>;
+
+pub(crate) fn value(ctx: &mut Context, tag: &str) -> String {
+    ctx.state().get_named::<String>(tag).cloned().unwrap_or_default()
+}
+
+/// The amount of `currency` in a currency input. Inputs that can be swapped to a second currency keep it
+/// converted under `{tag}Amount`; for the others the typed text is parsed.
+pub(crate) fn money(state: &mut State, tag: &str, currency: Currency) -> Option<Money> {
+    match state.get_named::<Option<Money>>(&format!("{tag}Amount")) {
+        Some(amount) => amount.filter(|m| m.currency() == currency),
+        None => {
+            let locale = state.get::<Locale>().copied().unwrap_or_default();
+            Money::parse_in(state.get_named::<String>(tag)?, currency, &locale)
+        },
+    }
+}
+
+pub(crate) fn number(value: &str) -> Option<f64> {
+    value.trim().trim_start_matches('$').replace(',', "").parse::<f64>().ok()
+}
+
+/// Parses the text produced by a date input, accepting `MM/DD/YYYY`, `MM/DD/YY` and `YYYY-MM-DD`.
+pub fn parse_date(value: &str) -> Option<NaiveDate> {
+    ["%m/%d/%Y", "%m/%d/%y", "%Y-%m-%d"].iter().find_map(|f| NaiveDate::parse_from_str(value.trim(), f).ok())
+}
+
+/// Parses the text produced by a time input, accepting 12-hour (`09:30 PM`) and 24-hour (`21:30`) formats.
+pub fn parse_time(value: &str) -> Option<NaiveTime> {
+    ["%I:%M %p", "%I:%M%p", "%H:%M", "%H:%M:%S"].iter().find_map(|f| NaiveTime::parse_from_str(value.trim(), f).ok())
+}
+
+/// Passes when every check passes, reporting the first failure otherwise.
+pub fn all(checks: Vec<Box<dyn ValidityFn>>) -> impl FnMut(&mut Context) -> Validation + Clone + 'static {
+    move |ctx: &mut Context| checks.clone().iter_mut().try_for_each(|check| (check)(ctx))
+}
+
+pub fn required(tag: &str) -> impl FnMut(&mut Context) -> Validation + Clone + 'static {
+    let tag = tag.to_string();
+    move |ctx: &mut Context| match value(ctx, &tag).trim().is_empty() {
+        true => Err("This field is required.".to_string()),
+        false => Ok(()),
+    }
+}
+
+pub fn min_length(tag: &str, min: usize) -> impl FnMut(&mut Context) -> Validation + Clone + 'static {
+    let tag = tag.to_string();
+    move |ctx: &mut Context| match value(ctx, &tag).chars().count() < min {
+        true => Err(format!("Must be at least {min} characters.")),
+        false => Ok(()),
+    }
+}
+
+pub fn max_length(tag: &str, max: usize) -> impl FnMut(&mut Context) -> Validation + Clone + 'static {
+    let tag = tag.to_string();
+    move |ctx: &mut Context| match value(ctx, &tag).chars().count() > max {
+        true => Err(format!("Must be {max} characters or fewer.")),
+        false => Ok(()),
+    }
+}
+
+/// Passes when the whole value matches `pattern`. Panics if `pattern` is not a valid regular expression.
+pub fn pattern(tag: &str, pattern: &str, message: &str) -> impl FnMut(&mut Context) -> Validation + Clone + 'static {
+    let tag = tag.to_string();
+    let message = message.to_string();
+    let regex = Regex::new(&format!("^(?:{pattern})$")).expect("invalid validation pattern");
+    move |ctx: &mut Context| match regex.is_match(&value(ctx, &tag)) {
+        true => Ok(()),
+        false => Err(message.clone()),
+    }
+}
+
+/// Passes when the value is empty or fills every digit of `mask`.
+pub fn mask(tag: &str, mask: Mask) -> impl FnMut(&mut Context) -> Validation + Clone + 'static {
+    let tag = tag.to_string();
+    move |ctx: &mut Context| {
+        let value = value(ctx, &tag);
+        match value.is_empty() || mask.is_complete(&value) {
+            true => Ok(()),
+            false => Err(format!("Enter the full value, like {}.", mask.pattern().replace('#', "0"))),
+        }
+    }
+}
+
+/// Passes when the value parses as an exact amount of `min`'s currency within `min..=max`.
+pub fn money_range(tag: &str, min: Money, max: Money) -> impl FnMut(&mut Context) -> Validation + Clone + 'static {
+    let tag = tag.to_string();
+    move |ctx: &mut Context| match money(ctx.state(), &tag, min.currency()) {
+        Some(m) if (min.minor()..=max.minor()).contains(&m.minor()) => Ok(()),
+        Some(_) => Err(format!("Enter an amount between {min} and {max}.")),
+        None => Err("Enter a valid amount.".to_string()),
+    }
+}
+
+/// Passes when the value parses as a number (currency symbols and separators allowed) within `min..=max`.
+pub fn range(tag: &str, min: f64, max: f64) -> impl FnMut(&mut Context) -> Validation + Clone + 'static {
+    let tag = tag.to_string();
+    move |ctx: &mut Context| match number(&value(ctx, &tag)) {
+        Some(n) if (min..=max).contains(&n) => Ok(()),
+        Some(_) => Err(format!("Enter a value between {min} and {max}.")),
+        None => Err("Enter a valid number.".to_string()),
+    }
+}
+
+/// Passes once an option has been picked for an enumerator.
+pub fn selected(tag: &str) -> impl FnMut(&mut Context) -> Validation + Clone + 'static {
+    let tag = tag.to_string();
+    move |ctx: &mut Context| match value(ctx, &tag).is_empty() {
+        true => Err("Choose an option to continue.".to_string()),
+        false => Ok(()),
+    }
+}
+
+/// Passes when at least `min` of the checkboxes stored under `tags` are checked.
+pub fn checked(tags: Vec<String>, min: usize) -> impl FnMut(&mut Context) -> Validation + Clone + 'static {
+    move |ctx: &mut Context| {
+        let count = tags.iter().filter(|tag| ctx.state().get_named::<bool>(tag).copied().unwrap_or_default()).count();
+        match count < min {
+            true if min == 1 => Err("Select at least 1 option.".to_string()),
+            true => Err(format!("Select at least {min} options.")),
+            false => Ok(()),
+        }
+    }
+}
+
+/// Passes when at least `min` entries of a multi-select picker are picked.
+pub fn picked(tag: &str, min: usize) -> impl FnMut(&mut Context) -> Validation + Clone + 'static {
+    let tag = tag.to_string();
+    move |ctx: &mut Context| {
+        let count = ctx.state().get_named::<Vec<String>>(&tag).map(|v| v.len()).unwrap_or_default();
+        match count < min {
+            true if min == 1 => Err("Pick at least 1 option.".to_string()),
+            true => Err(format!("Pick at least {min} options.")),
+            false => Ok(()),
+        }
+    }
+}
+
+/// Passes when the value is a valid date, optionally bounded by `min` and `max`.
+pub fn date(tag: &str, min: Option<NaiveDate>, max: Option<NaiveDate>) -> impl FnMut(&mut Context) -> Validation + Clone + 'static {
+    let tag = tag.to_string();
+    move |ctx: &mut Context| match parse_date(&value(ctx, &tag)) {
+        None => Err("Enter a valid date.".to_string()),
+        Some(d) if min.is_some_and(|m| d < m) => Err(format!("Date can't be before {}.", min.unwrap().format("%m/%d/%Y"))),
+        Some(d) if max.is_some_and(|m| d > m) => Err(format!("Date can't be after {}.", max.unwrap().format("%m/%d/%Y"))),
+        Some(_) => Ok(()),
+    }
+}
+
+/// Passes when the value is a valid time, optionally bounded by `min` and `max`.
+pub fn time(tag: &str, min: Option<NaiveTime>, max: Option<NaiveTime>) -> impl FnMut(&mut Context) -> Validation + Clone + 'static {
+    let tag = tag.to_string();
+    move |ctx: &mut Context| match parse_time(&value(ctx, &tag)) {
+        None => Err("Enter a valid time.".to_string()),
+        Some(t) if min.is_some_and(|m| t < m) => Err(format!("Time can't be before {}.", min.unwrap().format("%I:%M %p"))),
+        Some(t) if max.is_some_and(|m| t > m) => Err(format!("Time can't be after {}.", max.unwrap().format("%I:%M %p"))),
+        Some(_) => Ok(()),
+    }
+}
+
+/// Passes when the [`Secret`](crate::Secret) stored under `tag` is between `min` and `max` characters long.
+pub fn secret_length(tag: &str, min: usize, max: usize) -> impl FnMut(&mut Context) -> Validation + Clone + 'static {
+    let tag = tag.to_string();
+    move |ctx: &mut Context| {
+        let len = ctx.state().get_named::<Secret>(&tag).map(|s| s.len()).unwrap_or_default();
+        match len {
+            len if min == max && len != min => Err(format!("Must be exactly {min} characters.")),
+            len if len < min => Err(format!("Must be at least {min} characters.")),
+            len if len > max => Err(format!("Must be {max} characters or fewer.")),
+            _ => Ok(()),
+        }
+    }
+}
+
+/// Passes when the [`Secret`](crate::Secret) stored under `tag` is `count` words, each from `wordlist`.
+pub fn phrase(tag: &str, count: usize, wordlist: Rc<Vec<String>>) -> impl FnMut(&mut Context) -> Validation + Clone + 'static {
+    let tag = tag.to_string();
+    move |ctx: &mut Context| {
+        let Some(secret) = ctx.state().get_named::<Secret>(&tag) else { return Err(format!("Enter all {count} words.")) };
+        let words = secret.words();
+        match words.iter().position(|w| !wordlist.iter().any(|known| known == w)) {
+            Some(i) => Err(format!("Word {} is not a recovery word.", i + 1)),
+            None if words.len() != count => Err(format!("Enter all {count} words.")),
+            None => Ok(()),
+        }
+    }
+}
+
+/// Passes when a date-time input holds a valid date and time, optionally bounded by `min` and `max`.
+pub fn date_time(tag: &str, min: Option<NaiveDateTime>, max: Option<NaiveDateTime>) -> impl FnMut(&mut Context) -> Validation + Clone + 'static {
+    let tag = tag.to_string();
+    move |ctx: &mut Context| match ctx.state().get_named::<Option<NaiveDateTime>>(&tag).copied().flatten() {
+        None => Err("Enter a valid date and time.".to_string()),
+        Some(d) if min.is_some_and(|m| d < m) => Err(format!("Can't be before {}.", min.unwrap().format("%m/%d/%Y %I:%M %p"))),
+        Some(d) if max.is_some_and(|m| d > m) => Err(format!("Can't be after {}.", max.unwrap().format("%m/%d/%Y %I:%M %p"))),
+        Some(_) => Ok(()),
+    }
+}
+
+/// Passes when a date range input holds a start no later than its end, both within `min` and `max`.
+pub fn date_range(tag: &str, min: Option<NaiveDate>, max: Option<NaiveDate>) -> impl FnMut(&mut Context) -> Validation + Clone + 'static {
+    let tag = tag.to_string();
+    move |ctx: &mut Context| match ctx.state().get_named::<Option<DateRange>>(&tag).copied().flatten() {
+        None => Err("Enter a valid start and end date.".to_string()),
+        Some(r) if r.start > r.end => Err("The start date must be before the end date.".to_string()),
+        Some(r) if min.is_some_and(|m| r.start < m) => Err(format!("Dates can't be before {}.", min.unwrap().format("%m/%d/%Y"))),
+        Some(r) if max.is_some_and(|m| r.end > m) => Err(format!("Dates can't be after {}.", max.unwrap().format("%m/%d/%Y"))),
+        Some(_) => Ok(()),
+    }
+}